@@ -1,7 +1,9 @@
 use std::{
+    collections::HashMap,
     fs::{self, File},
     io::{BufWriter, ErrorKind, Write},
-    path::Path,
+    path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use color_eyre::{
@@ -9,6 +11,7 @@ use color_eyre::{
     Help, SectionExt,
 };
 use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
 
 use crate::tools::{Cargo, Sass, Tailwind, WasmBindgen};
 
@@ -108,13 +111,14 @@ fn transform_index(
     Ok(())
 }
 
-pub fn rust(
-    cargo: &Cargo,
-    project: &Path,
-    app_name: &str,
-    release: bool,
-    profile: &str,
-) -> Result<()> {
+/// Build every `bin` target in the workspace to WASM and run `wasm-bindgen` on each of the
+/// produced binaries. For a single target, the glue code is emitted right into the dist directory
+/// as before; for multiple targets, each gets its own sub-directory (named after the crate) so
+/// they don't clobber one another.
+///
+/// Returns the path to each produced `<crate_name>_bg.wasm` file, so callers (like minification)
+/// can iterate over every artifact.
+pub fn rust(cargo: &Cargo, project: &Path, release: bool, profile: &str) -> Result<Vec<PathBuf>> {
     cargo.run(project, release, profile)?;
 
     let bindgen = WasmBindgen::new(WasmBindgen::find_version(
@@ -124,26 +128,45 @@ pub fn rust(
         bindgen.install()?;
     }
 
-    bindgen.run(
-        &cargo.target_dir().join(format!(
-            "wazzup/wasm32-unknown-unknown/{profile}/{app_name}.wasm",
-            profile = if release { profile } else { "debug" },
-        )),
-        &project.join("dist"),
-    )?;
+    let profile_dir = if release { profile } else { "debug" };
+    let single_target = cargo.bin_targets().len() == 1;
+    let dist = project.join("dist");
 
-    Ok(())
+    cargo
+        .bin_targets()
+        .iter()
+        .map(|name| {
+            let target = cargo
+                .target_dir()
+                .join(format!("wazzup/wasm32-unknown-unknown/{profile_dir}/{name}.wasm"));
+            let out = if single_target {
+                dist.clone()
+            } else {
+                dist.join(name)
+            };
+
+            fs::create_dir_all(&out)?;
+            bindgen.run(&target, &out)?;
+
+            Ok(out.join(format!("{name}_bg.wasm")))
+        })
+        .collect()
 }
 
-pub fn sass(sass: &Sass, project: &Path, release: bool) -> Result<()> {
-    let stylesheets = [
+/// The stylesheet file that acts as the sass/scss build entrypoint, if the project has one.
+pub fn sass_entrypoint(project: &Path) -> Option<PathBuf> {
+    [
         project.join("assets/main.sass"),
         project.join("assets/main.scss"),
         project.join("assets/main.css"),
-    ];
+    ]
+    .into_iter()
+    .find(|path| path.exists())
+}
 
-    if let Some(stylesheet) = stylesheets.iter().find(|path| path.exists()) {
-        sass.run(stylesheet, &project.join("dist/main.css"), release)?;
+pub fn sass(sass: &Sass, project: &Path, release: bool) -> Result<()> {
+    if let Some(stylesheet) = sass_entrypoint(project) {
+        sass.run(&stylesheet, &project.join("dist/main.css"), release)?;
     }
 
     Ok(())
@@ -157,6 +180,63 @@ pub fn tailwind(tailwind: &Tailwind, project: &Path, release: bool) -> Result<()
     )
 }
 
+/// Cheap per-file fingerprint (size plus modification time), used to detect whether an asset needs
+/// to be re-copied into `dist/`, without hashing its content.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Deserialize, Serialize)]
+struct AssetFingerprint {
+    len: u64,
+    modified_nanos: u128,
+}
+
+impl AssetFingerprint {
+    fn of(metadata: &fs::Metadata) -> Result<Self> {
+        Ok(Self {
+            len: metadata.len(),
+            modified_nanos: metadata
+                .modified()?
+                .duration_since(SystemTime::UNIX_EPOCH)?
+                .as_nanos(),
+        })
+    }
+}
+
+/// Persisted map of every copied asset's path (relative to `assets/`) to the fingerprint it had the
+/// last time it was copied into `dist/`. Stored outside of `dist/`, since that directory is wiped on
+/// every full build, which would otherwise defeat the point of caching.
+#[derive(Default, Deserialize, Serialize)]
+struct AssetIndex {
+    files: HashMap<PathBuf, AssetFingerprint>,
+}
+
+impl AssetIndex {
+    /// Path, relative to the project root, where the index is persisted.
+    fn path(project: &Path) -> PathBuf {
+        project.join(".wazzup-cache/assets.json")
+    }
+
+    /// Load the persisted index, falling back to an empty one if it doesn't exist yet or can't be
+    /// parsed. Losing this cache never affects correctness, only how much gets re-copied, so any
+    /// read or parse failure is silently treated as a cold start rather than an error.
+    fn load(project: &Path) -> Self {
+        fs::read(Self::path(project))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, project: &Path) -> Result<()> {
+        let path = Self::path(project);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+
+        Ok(())
+    }
+}
+
 pub fn assets(project: &Path) -> Result<()> {
     let assets = project.join("assets");
 
@@ -181,6 +261,9 @@ pub fn assets(project: &Path) -> Result<()> {
     let assets = project.join("assets");
     let dist = project.join("dist");
 
+    let mut index = AssetIndex::load(project);
+    let mut seen = Vec::new();
+
     for entry in walk.skip(1) {
         let entry = entry?;
         let metadata = entry.metadata()?;
@@ -190,42 +273,102 @@ pub fn assets(project: &Path) -> Result<()> {
         }
 
         let source_path = entry.path();
-        let target_path = dist.join(source_path.strip_prefix(&assets)?);
+        let rel_path = source_path.strip_prefix(&assets)?.to_owned();
+        let target_path = dist.join(&rel_path);
+        let fingerprint = AssetFingerprint::of(&metadata)?;
+
+        seen.push(rel_path.clone());
+
+        if target_path.exists() && index.files.get(&rel_path) == Some(&fingerprint) {
+            continue;
+        }
 
         if let Some(parent) = target_path.parent() {
             fs::create_dir_all(parent)?;
         }
 
         fs::copy(source_path, target_path)?;
+        index.files.insert(rel_path, fingerprint);
     }
 
+    for stale in index
+        .files
+        .keys()
+        .filter(|path| !seen.contains(path))
+        .cloned()
+        .collect::<Vec<_>>()
+    {
+        index.files.remove(&stale);
+
+        match fs::remove_file(dist.join(&stale)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    index.save(project)?;
+
     Ok(())
 }
 
-pub fn asset(project: &Path, asset: &Path) -> Result<()> {
+/// Copy (or remove) a single asset into `dist/`, mirroring what a full [`assets`] run would do for
+/// it. Returns whether anything was actually copied or removed, so callers can skip a reload signal
+/// when the file was already up to date (editors commonly rewrite a file on save without changing
+/// its bytes).
+pub fn asset(project: &Path, asset: &Path) -> Result<bool> {
     let full_path = project.join(asset);
-    let dist_path = project.join("dist").join(asset.strip_prefix("assets/")?);
+    let rel_path = asset.strip_prefix("assets/")?;
+    let dist_path = project.join("dist").join(rel_path);
+
+    let mut index = AssetIndex::load(project);
+    let mut changed = false;
 
     if full_path.exists() {
         let metadata = fs::metadata(&full_path)?;
         if metadata.is_dir() {
             fs::create_dir_all(dist_path)?;
         } else {
-            if let Some(parent) = dist_path.parent() {
-                fs::create_dir_all(parent)?;
-            }
+            let fingerprint = AssetFingerprint::of(&metadata)?;
 
-            fs::copy(full_path, dist_path)?;
-        }
+            if !dist_path.exists() || index.files.get(rel_path) != Some(&fingerprint) {
+                if let Some(parent) = dist_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
 
-        Ok(())
+                fs::copy(&full_path, dist_path)?;
+                index.files.insert(rel_path.to_owned(), fingerprint);
+                changed = true;
+            }
+        }
     } else {
-        match fs::remove_dir_all(dist_path) {
-            Ok(()) => Ok(()),
-            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
-            Err(e) => Err(e.into()),
+        // The source is already gone, so its type has to be inferred from the dist side instead,
+        // to call the matching removal function (`remove_dir_all` errors out when given a plain
+        // file on most platforms).
+        let result = match fs::symlink_metadata(&dist_path) {
+            Ok(metadata) if metadata.is_dir() => {
+                changed = true;
+                fs::remove_dir_all(&dist_path)
+            }
+            Ok(_) => {
+                changed = true;
+                fs::remove_file(&dist_path)
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
         }
+
+        index.files.remove(rel_path);
     }
+
+    index.save(project)?;
+
+    Ok(changed)
 }
 
 #[cfg(test)]
@@ -345,7 +488,7 @@ mod tests {
               font-size: 16pt
         "})?;
 
-        let sass = Sass::new(temp.path(), temp.path())?;
+        let sass = Sass::new(temp.path(), temp.path(), None)?;
         super::sass(&sass, temp.path(), true)?;
 
         temp.child("dist/main.css")
@@ -368,6 +511,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn build_assets_skips_unchanged_files() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child("assets/test.txt").write_str("original")?;
+
+        super::assets(temp.path())?;
+        temp.child("dist/test.txt").assert("original");
+
+        // The dist copy is modified independently of the source; since the source's fingerprint is
+        // unchanged, a second run must leave it alone instead of overwriting it again.
+        temp.child("dist/test.txt")
+            .write_str("modified externally")?;
+        super::assets(temp.path())?;
+
+        temp.child("dist/test.txt").assert("modified externally");
+
+        Ok(())
+    }
+
+    #[test]
+    fn build_assets_removes_stale_dist_entries() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child("assets/keep.txt").write_str("keep")?;
+        temp.child("assets/gone.txt").write_str("gone")?;
+
+        super::assets(temp.path())?;
+        temp.child("dist/keep.txt").assert("keep");
+        temp.child("dist/gone.txt").assert("gone");
+
+        std::fs::remove_file(temp.child("assets/gone.txt").path())?;
+        super::assets(temp.path())?;
+
+        temp.child("dist/keep.txt").assert("keep");
+        assert!(!temp.child("dist/gone.txt").path().exists());
+
+        Ok(())
+    }
+
     #[test]
     fn build_asset() -> Result<()> {
         let temp = assert_fs::TempDir::new()?;
@@ -379,4 +560,23 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn build_asset_removal_prunes_index() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child("assets/test.txt").write_str("test")?;
+
+        super::asset(temp.path(), Path::new("assets/test.txt"))?;
+        temp.child("dist/test.txt").assert("test");
+
+        std::fs::remove_file(temp.child("assets/test.txt").path())?;
+        super::asset(temp.path(), Path::new("assets/test.txt"))?;
+
+        assert!(!temp.child("dist/test.txt").path().exists());
+        assert!(!super::AssetIndex::load(temp.path())
+            .files
+            .contains_key(Path::new("test.txt")));
+
+        Ok(())
+    }
 }
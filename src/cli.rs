@@ -3,6 +3,7 @@
 use std::{
     fs::OpenOptions,
     io::{self, Write},
+    net::IpAddr,
     path::{Path, PathBuf},
 };
 
@@ -10,6 +11,8 @@ use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand, ValueHint};
 use clap_complete::Shell;
 use color_eyre::eyre::{ensure, Result, WrapErr};
 
+use crate::{watch::WatchMode, CssMode};
+
 #[derive(Debug, Parser)]
 #[command(about, author, version)]
 #[cfg_attr(test, derive(PartialEq))]
@@ -40,6 +43,16 @@ pub enum Command {
     Build(BuildArgs),
     /// Run a local server for development purposes.
     Dev(DevArgs),
+    /// Scaffold a new project in the given directory.
+    Init {
+        /// Target directory, that will be created if it doesn't exist yet, and must be empty
+        /// otherwise.
+        #[arg(value_hint = ValueHint::DirPath)]
+        path: PathBuf,
+        /// CSS framework to set up the new project for.
+        #[arg(long, value_enum, default_value = "sass")]
+        css: CssMode,
+    },
     /// Generate auto-completion scripts for various shells.
     Completions {
         /// Shell to generate an auto-completion script for.
@@ -95,14 +108,64 @@ pub struct DevArgs {
     /// The local TCP port to listen on.
     #[arg(long, short, default_value_t = 8080)]
     pub port: u16,
+    /// Network interface to bind the server to.
+    ///
+    /// The default only accepts connections from this machine. Pass `0.0.0.0` to additionally
+    /// accept connections from other devices on the local network, for example to preview the
+    /// project on a phone. Binding to anything other than the loopback address exposes the server
+    /// to your network, which only does the basics in terms of security.
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: IpAddr,
+    /// Open the default browser at the served URL once the server is ready.
+    #[arg(long)]
+    pub open: bool,
+    /// Strategy used to watch the project for file changes.
+    ///
+    /// `recursive` registers far fewer OS watch descriptors and should be preferred on large
+    /// projects, to avoid exhausting `inotify`'s `max_user_watches` limit on Linux. `non-recursive`
+    /// matches the watcher's original behavior, and can be used as a fallback if the recursive mode
+    /// misbehaves on a particular system.
+    #[arg(long, value_enum, default_value = "recursive")]
+    pub watch_mode: WatchMode,
+    /// Backend used to watch the project for file changes.
+    ///
+    /// `native` relies on kernel-level file system notifications (inotify, FSEvents,
+    /// `ReadDirectoryChangesW`) and is fast and low-overhead, but never receives events on some
+    /// network mounts, Docker bind mounts, or when crossing the WSL host/guest boundary. `polling`
+    /// works everywhere instead, at the cost of higher resource usage and rebuild latency bounded
+    /// by `--watch-poll-interval`.
+    #[arg(long, value_enum, default_value = "native")]
+    pub watch_backend: WatchBackendKind,
+    /// Interval, in milliseconds, on which the `polling` watch backend re-scans the project for
+    /// changes. Ignored when `--watch-backend native` is used.
+    #[arg(long, default_value_t = 1000)]
+    pub watch_poll_interval: u64,
 }
 
 impl Default for DevArgs {
     fn default() -> Self {
-        Self { port: 8080 }
+        Self {
+            port: 8080,
+            host: IpAddr::from([127, 0, 0, 1]),
+            open: false,
+            watch_mode: WatchMode::Recursive,
+            watch_backend: WatchBackendKind::Native,
+            watch_poll_interval: 1000,
+        }
     }
 }
 
+/// Command-line representation of [`crate::watch::WatchBackend`], without its poll interval, which
+/// is configured separately via [`DevArgs::watch_poll_interval`].
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum WatchBackendKind {
+    /// Kernel-level file system notifications.
+    Native,
+    /// Poll the file system for changes on a regular interval.
+    Polling,
+}
+
 /// Generate shell completions, written to the standard output.
 pub fn completions(shell: Shell) {
     clap_complete::generate(
@@ -0,0 +1,163 @@
+//! Cheap, persisted freshness tracking for individual rebuild steps (stylesheets, the WASM binary,
+//! `index.html`, ...), so the `dev` loop can skip a tool invocation, and the browser reload that
+//! would follow it, when a step's inputs haven't meaningfully changed since it last ran. Editors
+//! commonly rewrite a file on save without changing its bytes, and this avoids treating that as a
+//! real change.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use color_eyre::eyre::Result;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+
+/// Fingerprint of a build step's inputs. The file count and latest modification time are checked
+/// first, since both are effectively free; the content hash is only computed as a fallback, when
+/// that cheap check disagrees with what's stored — the same coarse-then-precise freshness strategy
+/// Cargo itself uses for deciding whether a crate needs recompiling.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+struct Fingerprint {
+    file_count: usize,
+    modified_nanos: u128,
+    hash: String,
+}
+
+impl Fingerprint {
+    /// The free part of the fingerprint: no file contents are read.
+    fn cheap(paths: &[PathBuf]) -> Result<(usize, u128)> {
+        let mut modified_nanos = 0;
+
+        for path in paths {
+            let modified = fs::metadata(path)?
+                .modified()?
+                .duration_since(SystemTime::UNIX_EPOCH)?
+                .as_nanos();
+            modified_nanos = modified_nanos.max(modified);
+        }
+
+        Ok((paths.len(), modified_nanos))
+    }
+
+    /// The expensive part: a content hash over every input, salted with its path so that renaming
+    /// or swapping two same-content files still counts as a change.
+    fn hash(paths: &[PathBuf]) -> Result<String> {
+        let mut hasher = blake3::Hasher::new();
+
+        for path in paths {
+            hasher.update(path.as_os_str().as_encoded_bytes());
+            hasher.update(&fs::read(path)?);
+        }
+
+        Ok(hasher.finalize().to_string())
+    }
+}
+
+/// Persisted map of build step name (e.g. `"rust"`, `"sass"`) to the fingerprint its inputs had the
+/// last time the step actually ran. Stored outside of `dist/`, since that directory is wiped on
+/// every full build, which would otherwise defeat the point of caching.
+#[derive(Default, Deserialize, Serialize)]
+struct Cache {
+    steps: HashMap<String, Fingerprint>,
+}
+
+impl Cache {
+    fn path(project: &Path) -> PathBuf {
+        project.join(".wazzup-cache/freshness.json")
+    }
+
+    /// Load the persisted cache, falling back to an empty one if it doesn't exist yet or can't be
+    /// parsed. Losing this cache never affects correctness, only how much gets rebuilt, so any read
+    /// or parse failure is silently treated as a cold start rather than an error.
+    fn load(project: &Path) -> Self {
+        fs::read(Self::path(project))
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, project: &Path) -> Result<()> {
+        let path = Self::path(project);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+
+        Ok(())
+    }
+}
+
+/// Run `build` for `step` only if `inputs` changed since the last time it ran for this project,
+/// persisting the new fingerprint when it did. Returns whether `build` actually ran.
+pub fn run_if_stale(
+    project: &Path,
+    step: &str,
+    inputs: &[PathBuf],
+    build: impl FnOnce() -> Result<()>,
+) -> Result<bool> {
+    let mut cache = Cache::load(project);
+    let (file_count, modified_nanos) = Fingerprint::cheap(inputs)?;
+
+    let cheap_unchanged = cache
+        .steps
+        .get(step)
+        .is_some_and(|prev| prev.file_count == file_count && prev.modified_nanos == modified_nanos);
+
+    if cheap_unchanged {
+        return Ok(false);
+    }
+
+    let fresh = Fingerprint {
+        file_count,
+        modified_nanos,
+        hash: Fingerprint::hash(inputs)?,
+    };
+
+    if cache
+        .steps
+        .get(step)
+        .is_some_and(|prev| prev.hash == fresh.hash)
+    {
+        // Mtime moved but the content didn't (e.g. a touch, or an editor rewriting the file on
+        // save); still worth updating the cheap fields so the next check can skip the hash again.
+        cache.steps.insert(step.to_owned(), fresh);
+        cache.save(project)?;
+        return Ok(false);
+    }
+
+    build()?;
+
+    cache.steps.insert(step.to_owned(), fresh);
+    cache.save(project)?;
+
+    Ok(true)
+}
+
+/// Collect every file under `root` for which `is_match` returns `true`, skipping anything ignored
+/// by `.gitignore`/`.ignore`/`.git/info/exclude`.
+pub fn collect_files(root: &Path, is_match: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
+    let walk = WalkBuilder::new(root)
+        .standard_filters(false)
+        .require_git(false)
+        .git_ignore(true)
+        .git_exclude(true)
+        .ignore(true)
+        .build();
+
+    let mut files = Vec::new();
+
+    for entry in walk {
+        let entry = entry?;
+
+        if entry.file_type().is_some_and(|kind| kind.is_file()) && is_match(entry.path()) {
+            files.push(entry.path().to_owned());
+        }
+    }
+
+    Ok(files)
+}
@@ -0,0 +1,122 @@
+//! Scaffolding for new projects, so a first-time user can go from an empty directory to a running
+//! `wazzup dev` server without hand-assembling the `Cargo.toml`, `index.html` and stylesheet setup
+//! that [`crate::build`] and [`crate::css_mode`] expect.
+
+use std::{fs, io::ErrorKind, path::Path};
+
+use color_eyre::eyre::{ensure, Result, WrapErr};
+
+use crate::CssMode;
+
+/// Minimal `wasm-bindgen` example, shared with the project's own `sample` crate, that prints
+/// `Hello, world!` to the console and the page.
+const MAIN_RS: &str = include_str!("../sample/src/main.rs");
+
+const INDEX_HTML: &str = r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <title>wazzup</title>
+    <!--WAZZUP-HEAD-->
+  </head>
+  <body>
+    <!--WAZZUP-BODY-->
+  </body>
+</html>
+"#;
+
+const MAIN_SCSS: &str = "body {\n  font-family: sans-serif;\n}\n";
+
+const TAILWIND_CONFIG_JS: &str = r#"/** @type {import('tailwindcss').Config} */
+module.exports = {
+  content: ["./index.html", "./src/**/*.rs"],
+  theme: {
+    extend: {},
+  },
+  plugins: [],
+};
+"#;
+
+const TAILWIND_MAIN_CSS: &str = "@tailwind base;\n@tailwind components;\n@tailwind utilities;\n";
+
+const GITIGNORE: &str = "/target\n/dist\n/.wazzup-cache\nCargo.lock\n";
+
+/// Scaffold a new project at `path`, set up for the given CSS framework. The directory is created
+/// if it doesn't exist yet; if it does, it must be empty, mirroring the careful file-creation
+/// behavior of [`crate::cli::manpages`].
+pub fn init(path: &Path, css: CssMode) -> Result<()> {
+    match fs::read_dir(path) {
+        Ok(mut entries) => {
+            ensure!(entries.next().is_none(), "target directory is not empty");
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            fs::create_dir_all(path).wrap_err("failed creating the target directory")?;
+        }
+        Err(e) => return Err(e).wrap_err("failed reading the target directory"),
+    }
+
+    let name = package_name(path);
+
+    fs::write(path.join("Cargo.toml"), cargo_toml(&name))
+        .wrap_err("failed writing Cargo.toml")?;
+    fs::write(path.join("index.html"), INDEX_HTML).wrap_err("failed writing index.html")?;
+    fs::write(path.join(".gitignore"), GITIGNORE).wrap_err("failed writing .gitignore")?;
+
+    fs::create_dir(path.join("src")).wrap_err("failed creating the src directory")?;
+    fs::write(path.join("src/main.rs"), MAIN_RS).wrap_err("failed writing src/main.rs")?;
+
+    fs::create_dir(path.join("assets")).wrap_err("failed creating the assets directory")?;
+
+    match css {
+        CssMode::Sass => {
+            fs::write(path.join("assets/main.scss"), MAIN_SCSS)
+                .wrap_err("failed writing assets/main.scss")?;
+        }
+        CssMode::Tailwind => {
+            fs::write(path.join("tailwind.config.js"), TAILWIND_CONFIG_JS)
+                .wrap_err("failed writing tailwind.config.js")?;
+            fs::write(path.join("assets/main.css"), TAILWIND_MAIN_CSS)
+                .wrap_err("failed writing assets/main.css")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Derive a crate name from the target directory's name, falling back to a generic name if it is
+/// missing or not a valid crate name (for example when scaffolding into `.`).
+fn package_name(path: &Path) -> String {
+    let raw = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("wazzup-app");
+
+    let name: String = raw
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+
+    if name.is_empty() || name.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        "wazzup-app".to_owned()
+    } else {
+        name
+    }
+}
+
+fn cargo_toml(name: &str) -> String {
+    format!(
+        r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+wasm-bindgen = "0.2"
+
+[dependencies.web-sys]
+version = "0.3"
+features = ["Document", "Element", "HtmlElement", "Window"]
+"#
+    )
+}
@@ -18,15 +18,18 @@ use tracing_subscriber::{filter::Targets, prelude::*};
 
 use self::{
     cli::{BuildArgs, Command, DevArgs},
+    server::BuildOutcome,
     watch::ChangeType,
 };
 use crate::{
     cli::Cli,
-    tools::{Cargo, Rustup, Sass, Tailwind},
+    tools::{Cargo, Rustup, Sass, Tailwind, ToolVersions, WasmOpt, WasmOptSettings},
 };
 
 mod build;
 mod cli;
+mod freshness;
+mod init;
 mod minify;
 mod server;
 mod status;
@@ -47,6 +50,7 @@ fn main() -> Result<()> {
         Command::Status => status::status(&std::env::current_dir()?),
         Command::Build(args) => build(args, false),
         Command::Dev(args) => dev(args),
+        Command::Init { path, css } => init::init(&path, css),
         Command::Completions { shell } => cli::completions(shell),
         Command::Manpages { dir } => cli::manpages(&dir),
     }
@@ -97,8 +101,8 @@ fn package_name(project: &Path) -> Result<String> {
 
 /// The CSS framework that is used by the project. This decides what tools are run when building
 /// all components of the project.
-#[derive(Clone, Copy, Eq, PartialEq)]
-enum CssMode {
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub(crate) enum CssMode {
     /// The [SASS/SCSS](https://sass-lang.com) framework.
     Sass,
     /// The [TailwindCSS](https://tailwindcss.com) framework.
@@ -142,17 +146,19 @@ fn build(args: BuildArgs, dev: bool) -> Result<()> {
     let cargo = Cargo::new(&project)?;
     let name = package_name(&project)?;
     let css_mode = css_mode(&project)?;
+    let tools = ToolVersions::load(&project)?;
 
     build::index(&project, &name, args.release, &args.base_url, dev)?;
     info!("built index.html");
 
     match css_mode {
         CssMode::Sass => {
-            let sass = Sass::new(cargo.workspace_dir(), &project)?;
+            let sass = Sass::new(cargo.workspace_dir(), &project, tools.sass.as_deref())?;
             build::sass(&sass, &project, args.release)?
         }
         CssMode::Tailwind => {
-            let tailwind = Tailwind::new(cargo.workspace_dir(), &project)?;
+            let tailwind =
+                Tailwind::new(cargo.workspace_dir(), &project, tools.tailwindcss.as_deref())?;
             build::tailwind(&tailwind, &project, args.release)?
         }
     }
@@ -161,15 +167,17 @@ fn build(args: BuildArgs, dev: bool) -> Result<()> {
     build::assets(&project)?;
     info!("built assets");
 
-    build::rust(&cargo, &project, &name, args.release, &args.profile)?;
-    info!("built WASM files");
+    let artifacts = build::rust(&cargo, &project, args.release, &args.profile)?;
+    info!(count = artifacts.len(), "built WASM files");
 
     if args.release {
         let reduction = minify::html(&project)?;
         info!(%reduction, "minified HTML files");
         let reduction = minify::js(&project)?;
         info!(%reduction, "minified JavaScript files");
-        let reduction = minify::wasm(&project)?;
+        let wasm_opt = WasmOpt::new(tools.wasm_opt.as_deref())?;
+        let wasm_opt_settings = WasmOptSettings::load(&project)?;
+        let reduction = minify::wasm(&project, &wasm_opt, &wasm_opt_settings)?;
         info!(%reduction, "minified WASM files");
     }
 
@@ -182,24 +190,37 @@ fn dev(args: DevArgs) -> Result<()> {
     let name = package_name(&project)?;
     let css_mode = css_mode(&project)?;
 
-    let watcher = watch::watch(project.clone())?;
+    let backend = match args.watch_backend {
+        cli::WatchBackendKind::Native => watch::WatchBackend::Native,
+        cli::WatchBackendKind::Polling => {
+            watch::WatchBackend::Polling(Duration::from_millis(args.watch_poll_interval))
+        }
+    };
+    let watcher = watch::watch(project.clone(), args.watch_mode, backend)?;
     let debouncer = watch::debounce(watcher, Duration::from_secs(2))?;
     let (shutdown_tx, shutdown_rx) = flume::bounded(0);
-    let (reload_tx, reload_rx) = flume::bounded(0);
+    let (reload_tx, reload_rx) = flume::bounded::<BuildOutcome>(0);
 
     let thread = thread::spawn({
         let project = project.clone();
         let cargo = Cargo::new(&project)?;
-        let sass = Sass::new(cargo.workspace_dir(), &project)?;
-        let tailwind = Tailwind::new(cargo.workspace_dir(), &project)?;
+        let tools = ToolVersions::load(&project)?;
+        let sass = Sass::new(cargo.workspace_dir(), &project, tools.sass.as_deref())?;
+        let tailwind =
+            Tailwind::new(cargo.workspace_dir(), &project, tools.tailwindcss.as_deref())?;
 
         move || {
             if let Err(e) = build(BuildArgs::default(), true) {
                 error!(error = ?e, "failed building");
+                reload_tx
+                    .send(BuildOutcome::Failed {
+                        report: format!("{e:?}"),
+                    })
+                    .ok();
                 return;
             }
 
-            reload_tx.send(()).ok();
+            reload_tx.send(BuildOutcome::Changed(ChangeType::Rust)).ok();
             debug!("sent reload signal");
 
             loop {
@@ -209,15 +230,31 @@ fn dev(args: DevArgs) -> Result<()> {
                     .wait();
 
                 if let Some(change) = res {
-                    if let Err(e) =
-                        rebuild(&cargo, &sass, &tailwind, &project, &name, css_mode, change)
-                    {
-                        error!(error = ?e, "failed rebuilding");
-                        continue;
+                    match rebuild(
+                        &cargo,
+                        &sass,
+                        &tailwind,
+                        &project,
+                        &name,
+                        css_mode,
+                        change.clone(),
+                    ) {
+                        Ok(true) => {
+                            reload_tx.send(BuildOutcome::Changed(change)).ok();
+                            debug!("sent reload signal");
+                        }
+                        Ok(false) => {
+                            debug!("inputs unchanged, skipping reload signal");
+                        }
+                        Err(e) => {
+                            error!(error = ?e, "failed rebuilding");
+                            reload_tx
+                                .send(BuildOutcome::Failed {
+                                    report: format!("{e:?}"),
+                                })
+                                .ok();
+                        }
                     }
-
-                    reload_tx.send(()).ok();
-                    debug!("sent reload signal");
                 } else {
                     debouncer.shutdown().shutdown();
                     break;
@@ -226,7 +263,7 @@ fn dev(args: DevArgs) -> Result<()> {
         }
     });
 
-    let res = server::run(project, args.port, reload_rx);
+    let res = server::run(project, args.host, args.port, args.open, reload_rx);
 
     shutdown_tx.send(()).ok();
     thread.join().expect("thread to shut down properly");
@@ -237,6 +274,11 @@ fn dev(args: DevArgs) -> Result<()> {
 /// Rebuild parts of the application, based on the kind of source files that changed. For example,
 /// only rebuild the WASM binary if Rust code changed or only the stylesheets if any sass/scss/css
 /// files changed.
+///
+/// Each step is skipped, without even running its tool, if its inputs are unchanged since it last
+/// ran (see [`freshness`]) — editors commonly rewrite a file on save without changing its bytes.
+/// Returns whether anything actually rebuilt, so the caller knows whether a reload signal is
+/// warranted.
 fn rebuild(
     cargo: &Cargo,
     sass: &Sass,
@@ -245,7 +287,9 @@ fn rebuild(
     name: &str,
     css_mode: CssMode,
     change: ChangeType,
-) -> Result<()> {
+) -> Result<bool> {
+    let mut changed = false;
+
     // Tailwind scans project files to detect what CSS classes are used. Therefore, we have to run
     // it not just when CSS files changed, but when HTML or Rust files changed as well.
     if css_mode == CssMode::Tailwind
@@ -254,30 +298,78 @@ fn rebuild(
             ChangeType::Html | ChangeType::Css | ChangeType::Rust
         )
     {
-        build::tailwind(tailwind, project, false)?;
-        info!(mode = %css_mode, "rebuilt stylesheets");
+        let mut inputs = freshness::collect_files(project, |path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("rs" | "html")
+            )
+        })?;
+        // Tailwind's own input stylesheet (the `@tailwind` directives) is not `.rs`/`.html`, but a
+        // change to it must still invalidate the freshness cache.
+        inputs.push(project.join("assets/main.css"));
+
+        if freshness::run_if_stale(project, "tailwind", &inputs, || {
+            build::tailwind(tailwind, project, false)
+        })? {
+            info!(mode = %css_mode, "rebuilt stylesheets");
+            changed = true;
+        }
     }
 
     match change {
         ChangeType::Html => {
-            build::index(project, name, false, "/", true)?;
-            info!("rebuilt index.html");
+            let inputs = vec![project.join("index.html")];
+
+            if freshness::run_if_stale(project, "html", &inputs, || {
+                build::index(project, name, false, "/", true)
+            })? {
+                info!("rebuilt index.html");
+                changed = true;
+            }
         }
         ChangeType::Css => {
             if css_mode == CssMode::Sass {
-                build::sass(sass, project, false)?;
-                info!(mode = %css_mode, "rebuilt stylesheets");
+                // The entrypoint alone isn't enough: it commonly `@use`/`@import`s partials
+                // elsewhere under `assets/`, and editing one of those must invalidate the
+                // freshness cache just as much as editing the entrypoint itself.
+                let inputs = freshness::collect_files(project, |path| {
+                    matches!(
+                        path.extension().and_then(|ext| ext.to_str()),
+                        Some("sass" | "scss" | "css")
+                    )
+                })?;
+
+                if freshness::run_if_stale(project, "sass", &inputs, || {
+                    build::sass(sass, project, false)
+                })? {
+                    info!(mode = %css_mode, "rebuilt stylesheets");
+                    changed = true;
+                }
             }
         }
         ChangeType::Static(asset) => {
-            build::asset(project, asset.strip_prefix(project)?)?;
-            info!("rebuilt asset");
+            if build::asset(project, asset.strip_prefix(project)?)? {
+                info!("rebuilt asset");
+                changed = true;
+            }
         }
         ChangeType::Rust => {
-            build::rust(cargo, project, name, false, "release")?;
-            info!("rebuilt WASM files");
+            let inputs = freshness::collect_files(project, |path| {
+                path.extension().is_some_and(|ext| ext == "rs")
+                    || matches!(
+                        path.file_name().and_then(|name| name.to_str()),
+                        Some("Cargo.toml" | "Cargo.lock")
+                    )
+            })?;
+
+            if freshness::run_if_stale(project, "rust", &inputs, || {
+                build::rust(cargo, project, false, "release").map(|_| ())
+            })? {
+                info!("rebuilt WASM files");
+                changed = true;
+            }
         }
     }
 
-    Ok(())
+    Ok(changed)
 }
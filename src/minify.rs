@@ -7,7 +7,7 @@ use std::{
 use anyhow::{anyhow, Result};
 use ignore::{DirEntry, WalkBuilder};
 
-use crate::tools::WasmOpt;
+use crate::tools::{WasmOpt, WasmOptSettings};
 
 #[derive(Default)]
 pub struct Reduction {
@@ -81,7 +81,7 @@ pub fn js(project: &Path) -> Result<Reduction> {
     Ok(reduction)
 }
 
-pub fn wasm(project: &Path) -> Result<Reduction> {
+pub fn wasm(project: &Path, wasm_opt: &WasmOpt, settings: &WasmOptSettings) -> Result<Reduction> {
     let mut reduction = Reduction::default();
 
     for file in find_files(project.join("dist"), "wasm") {
@@ -89,7 +89,7 @@ pub fn wasm(project: &Path) -> Result<Reduction> {
 
         reduction.original += metadata.len() as usize;
 
-        WasmOpt::run(entry.path())?;
+        wasm_opt.run(entry.path(), settings)?;
 
         reduction.minified += entry.metadata()?.len() as usize;
     }
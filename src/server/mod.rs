@@ -1,6 +1,11 @@
 //! Local server, to host the project for development purposes.
 
-use std::{future::IntoFuture, net::Ipv4Addr, path::PathBuf, time::Duration};
+use std::{
+    future::IntoFuture,
+    net::{IpAddr, Ipv4Addr, UdpSocket},
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use axum::{
     extract::{
@@ -13,39 +18,126 @@ use axum::{
     Router,
 };
 use color_eyre::eyre::Result;
+use serde::Serialize;
 use tokio::{net::TcpListener, sync::watch, time};
 use tokio_shutdown::Shutdown;
 use tower_http::services::{ServeDir, ServeFile};
-use tracing::debug;
+use tracing::{debug, info, warn};
+
+use crate::watch::ChangeType;
+
+/// Outcome of a build (or rebuild) step in the `dev` loop, as reported to the server.
+pub enum BuildOutcome {
+    /// The build succeeded, and the given kind of change should be reflected in the browser.
+    Changed(ChangeType),
+    /// The build failed; `report` is the formatted error, to be shown to the developer.
+    Failed { report: String },
+}
+
+/// Notification sent to `reload.js` over the `/__WAZZUP__/reload` WebSocket, describing how little
+/// (or how much) of the page needs refreshing for a given [`ChangeType`], or that a build failed.
+///
+/// Serialized as e.g. `{"kind":"css"}`, `{"kind":"static","path":"/logo.png"}`, or
+/// `{"kind":"error","message":"..."}`.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ReloadKind {
+    /// A stylesheet changed; every `<link rel="stylesheet">` can be swapped in place.
+    Css,
+    /// The asset at `path` (root-relative, as served from `dist/`) changed; matching `<img>`/
+    /// `<source>` elements can be swapped in place.
+    Static { path: String },
+    /// HTML structure or the WASM binary changed; nothing short of a full navigation is safe.
+    Full,
+    /// A build step failed; the frontend should show `message` as a full-screen overlay instead
+    /// of reloading anything.
+    Error { message: String },
+}
+
+impl ReloadKind {
+    /// Map a raw file system [`ChangeType`] to the reload strategy the frontend should use for it.
+    /// `project` is needed to turn [`ChangeType::Static`]'s absolute source path into the
+    /// root-relative URL the file is actually served at from `dist/`.
+    fn from_change(project: &Path, change: &ChangeType) -> Self {
+        match change {
+            ChangeType::Css => Self::Css,
+            ChangeType::Static(path) => {
+                let path = path
+                    .strip_prefix(project.join("assets"))
+                    .unwrap_or(path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+
+                Self::Static {
+                    path: format!("/{path}"),
+                }
+            }
+            ChangeType::Html | ChangeType::Rust => Self::Full,
+        }
+    }
 
-pub fn run(base: PathBuf, port: u16, rebuild: flume::Receiver<()>) -> Result<()> {
+    /// Map a [`BuildOutcome`] reported by the `dev` loop to the message sent to the frontend.
+    fn from_outcome(project: &Path, outcome: &BuildOutcome) -> Self {
+        match outcome {
+            BuildOutcome::Changed(change) => Self::from_change(project, change),
+            BuildOutcome::Failed { report } => Self::Error {
+                message: report.clone(),
+            },
+        }
+    }
+}
+
+pub fn run(
+    base: PathBuf,
+    host: IpAddr,
+    port: u16,
+    open: bool,
+    rebuild: flume::Receiver<BuildOutcome>,
+) -> Result<()> {
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()?
         .block_on(async {
-            let (tx, rx) = watch::channel(());
-            tokio::spawn(async move {
-                loop {
-                    if rebuild.recv_async().await.is_err() {
-                        break;
-                    }
-                    if tx.send(()).is_err() {
-                        break;
+            let (tx, rx) = watch::channel(ReloadKind::Full);
+            tokio::spawn({
+                let base = base.clone();
+                async move {
+                    loop {
+                        let Ok(outcome) = rebuild.recv_async().await else {
+                            break;
+                        };
+                        if tx.send(ReloadKind::from_outcome(&base, &outcome)).is_err() {
+                            break;
+                        }
                     }
                 }
             });
 
-            run_server(base, port, rx).await
+            run_server(base, host, port, open, rx).await
         })
 }
 
+/// Best-effort guess at this machine's LAN IP address, by asking the OS which local address it
+/// would use to reach the internet. Doesn't actually send any traffic.
+fn lan_address() -> Option<IpAddr> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((Ipv4Addr::new(1, 1, 1, 1), 80)).ok()?;
+    Some(socket.local_addr().ok()?.ip())
+}
+
 #[derive(Clone)]
 struct AppState {
     shutdown: Shutdown,
-    reload: watch::Receiver<()>,
+    reload: watch::Receiver<ReloadKind>,
 }
 
-async fn run_server(base: PathBuf, port: u16, notifier: watch::Receiver<()>) -> Result<()> {
+async fn run_server(
+    base: PathBuf,
+    host: IpAddr,
+    port: u16,
+    open: bool,
+    notifier: watch::Receiver<ReloadKind>,
+) -> Result<()> {
     let index = ServeFile::new(base.join("dist/index.html"));
     let dist = ServeDir::new(base.join("dist"));
     let shutdown = Shutdown::new()?;
@@ -62,9 +154,34 @@ async fn run_server(base: PathBuf, port: u16, notifier: watch::Receiver<()>) ->
             reload: notifier,
         });
 
-    // Always run on localhost only. It's a bad idea to publicly expose this server,
-    // due to only doing the basics in terms of security.
-    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await?;
+    if !host.is_loopback() {
+        warn!(%host, "binding to a non-loopback interface exposes this server on your network, which only does the basics in terms of security");
+    }
+
+    let listener = TcpListener::bind((host, port)).await?;
+    // `0.0.0.0`/`::` isn't itself a connectable address; substitute the loopback address, which is
+    // reachable whenever an unspecified host is bound. A specific host (loopback or LAN) is used
+    // as-is, since that's the only address the socket actually accepts connections on.
+    let display_host = if host.is_unspecified() {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    } else {
+        host
+    };
+    let local_url = format!("http://{display_host}:{port}");
+    info!(url = %local_url, "server listening");
+
+    if host.is_unspecified() {
+        if let Some(lan_ip) = lan_address() {
+            info!(url = %format!("http://{lan_ip}:{port}"), "also reachable on the local network");
+        }
+    }
+
+    if open {
+        if let Err(e) = open::that(&local_url) {
+            warn!(error = %e, "failed opening the browser");
+        }
+    }
+
     let server = axum::serve(listener, app).into_future();
 
     tokio::select! {
@@ -99,8 +216,12 @@ async fn reload_ws(
 }
 
 /// Notification logic, that listens for rebuilds on any components (triggered due to file changes)
-/// and then notifies the frontend to reload.
-async fn ws_notify(mut socket: WebSocket, shutdown: Shutdown, mut reload: watch::Receiver<()>) {
+/// and then notifies the frontend how to reload, depending on the kind of change.
+async fn ws_notify(
+    mut socket: WebSocket,
+    shutdown: Shutdown,
+    mut reload: watch::Receiver<ReloadKind>,
+) {
     loop {
         tokio::select! {
             () = shutdown.handle() => {
@@ -112,7 +233,10 @@ async fn ws_notify(mut socket: WebSocket, shutdown: Shutdown, mut reload: watch:
                     return;
                 }
 
-                let msg = Message::text("reload");
+                let kind = reload.borrow_and_update().clone();
+                let msg = Message::text(
+                    serde_json::to_string(&kind).expect("ReloadKind must always serialize"),
+                );
 
                 // ensure we don't wait too long, so we don't miss out on any shutdown signal
                 if time::timeout(Duration::from_secs(1), socket.send(msg)).await.is_err() {
@@ -3,6 +3,7 @@
 use std::{
     fmt::{self, Display},
     path::{Path, PathBuf},
+    process::Command,
 };
 
 use color_eyre::Result;
@@ -12,21 +13,42 @@ use tabled::{
 };
 use yansi::{Color, Paint};
 
-use crate::tools::{self, Cargo};
+use crate::tools::{self, Cargo, Sass, Tailwind, ToolVersions, WasmBindgen, WasmOpt};
 
 /// Display the status of required external tools, and mandatory files within the current project.
 pub fn status(project: &Path) -> Result<()> {
     let cargo = Cargo::new(project)?;
     let root = cargo.workspace_dir();
+    let pinned = ToolVersions::load(project)?;
 
     print_table(
         "Tools",
         [
-            tool_status_sys("rustup")?,
-            tool_status_sys("cargo")?,
-            tool_status_sys("wasm-opt")?,
-            tool_status_js("sass", root, project)?,
-            tool_status_js("tailwindcss", root, project)?,
+            tool_status_sys("rustup", None, None, None)?,
+            tool_status_sys("cargo", None, None, None)?,
+            tool_status_sys(
+                "wasm-opt",
+                pinned.wasm_opt.as_deref(),
+                Some(WasmOpt::cache_path),
+                Some(probe_flag_version),
+            )?,
+            tool_status_js(
+                "sass",
+                root,
+                project,
+                pinned.sass.as_deref(),
+                Some(Sass::cache_path),
+                Some(probe_flag_version),
+            )?,
+            tool_status_js(
+                "tailwindcss",
+                root,
+                project,
+                pinned.tailwindcss.as_deref(),
+                Some(Tailwind::cache_path),
+                Some(probe_help_version),
+            )?,
+            wasm_bindgen_status(&cargo)?,
         ],
     );
 
@@ -72,6 +94,10 @@ struct Tool {
     /// Absolute path to the tool for invocation.
     #[tabled(display_with = "display_pathbuf_opt")]
     path: Option<PathBuf>,
+    /// Version of the tool, as discovered by running its version probe, or else the version pinned
+    /// or required for the project. Empty if neither is known.
+    #[tabled(display_with = "display_version_opt")]
+    version: Option<String>,
 }
 
 /// Information about a single project file.
@@ -84,12 +110,15 @@ struct ProjectFile {
     status: FileStatus,
 }
 
-/// Status of a file.
+/// Status of a file, or the version-pinning state of a tool.
 enum FileStatus {
     /// File was found.
     Found,
     /// File is missing.
     Missing,
+    /// The tool was found, but its version doesn't match the version required or pinned for the
+    /// project.
+    Mismatch,
 }
 
 impl Display for FileStatus {
@@ -100,6 +129,7 @@ impl Display for FileStatus {
             match self {
                 Self::Found => Paint::new("found").fg(Color::Green),
                 Self::Missing => Paint::new("missing").fg(Color::Red),
+                Self::Mismatch => Paint::new("mismatch").fg(Color::Yellow),
             }
         )
     }
@@ -119,25 +149,177 @@ fn display_pathbuf_opt(v: &Option<PathBuf>) -> String {
     }
 }
 
-/// Determine the installation status of an external, system-installed tool.
-fn tool_status_sys(name: &'static str) -> Result<Tool> {
-    tool_status(name, tools::find_bin)
+/// Helper for [`tabled`], to display an [`Option`]<[`String`]>.
+#[expect(clippy::ref_option)]
+fn display_version_opt(v: &Option<String>) -> String {
+    v.clone().unwrap_or_default()
 }
 
-fn tool_status_js(name: &'static str, root: &Path, cwd: &Path) -> Result<Tool> {
-    tool_status(name, |name| tools::find_bin_js(name, root, cwd))
+/// Determine the installation status of an external tool, usually looked up on the system, unless
+/// `required` pins a version, in which case `cache_path` resolves the path of the binary a build
+/// would actually use (downloaded into the cache directory, not the system one), mirroring how
+/// [`wasm_bindgen_status`] already works.
+fn tool_status_sys(
+    name: &'static str,
+    required: Option<&str>,
+    cache_path: Option<fn(&str) -> Result<PathBuf>>,
+    probe: Option<fn(&Path) -> Result<Option<String>>>,
+) -> Result<Tool> {
+    tool_status(name, tools::find_bin, required, cache_path, probe)
+}
+
+fn tool_status_js(
+    name: &'static str,
+    root: &Path,
+    cwd: &Path,
+    required: Option<&str>,
+    cache_path: Option<fn(&str) -> Result<PathBuf>>,
+    probe: Option<fn(&Path) -> Result<Option<String>>>,
+) -> Result<Tool> {
+    tool_status(
+        name,
+        |name| tools::find_bin_js(name, root, cwd),
+        required,
+        cache_path,
+        probe,
+    )
 }
 
-fn tool_status(name: &'static str, find: impl Fn(&'static str) -> Result<PathBuf>) -> Result<Tool> {
-    let (path, status) = match find(name) {
-        Ok(path) => (Some(path), FileStatus::Found),
+fn tool_status(
+    name: &'static str,
+    find: impl Fn(&'static str) -> Result<PathBuf>,
+    required: Option<&str>,
+    cache_path: Option<fn(&str) -> Result<PathBuf>>,
+    probe: Option<fn(&Path) -> Result<Option<String>>>,
+) -> Result<Tool> {
+    match (required, cache_path) {
+        (Some(version), Some(cache_path)) => {
+            tool_status_pinned(name, cache_path(version)?, version, probe)
+        }
+        _ => tool_status_system(name, find, probe),
+    }
+}
+
+/// Determine the installation status of a tool looked up on the system (or, for JS tools, in
+/// `node_modules/.bin`), used whenever no version is pinned for it.
+fn tool_status_system(
+    name: &'static str,
+    find: impl Fn(&'static str) -> Result<PathBuf>,
+    probe: Option<fn(&Path) -> Result<Option<String>>>,
+) -> Result<Tool> {
+    let (path, version, status) = match find(name) {
+        Ok(path) => {
+            let version = probe.and_then(|probe| probe(&path).ok().flatten());
+            (Some(path), version, FileStatus::Found)
+        }
         Err(report) => match report.downcast_ref::<which::Error>() {
-            Some(which::Error::CannotFindBinaryPath) => (None, FileStatus::Missing),
+            Some(which::Error::CannotFindBinaryPath) => (None, None, FileStatus::Missing),
             _ => return Err(report),
         },
     };
 
-    Ok(Tool { name, status, path })
+    Ok(Tool {
+        name,
+        status,
+        path,
+        version,
+    })
+}
+
+/// Determine the installation status of a tool pinned to a specific `version`, probing the binary
+/// at `bin_path` (the cache location a build would actually use) rather than doing a system
+/// lookup. `version` is compared against the probed version to catch a stale or corrupted cache
+/// entry.
+fn tool_status_pinned(
+    name: &'static str,
+    bin_path: PathBuf,
+    version: &str,
+    probe: Option<fn(&Path) -> Result<Option<String>>>,
+) -> Result<Tool> {
+    if !bin_path.exists() {
+        return Ok(Tool {
+            name,
+            status: FileStatus::Missing,
+            path: None,
+            version: Some(version.to_owned()),
+        });
+    }
+
+    let probed = probe.and_then(|probe| probe(&bin_path).ok().flatten());
+    let status = match &probed {
+        Some(probed) if !versions_match(probed, version) => FileStatus::Mismatch,
+        _ => FileStatus::Found,
+    };
+
+    Ok(Tool {
+        name,
+        status,
+        path: Some(bin_path),
+        version: probed.or_else(|| Some(version.to_owned())),
+    })
+}
+
+/// Determine the installation status of the `wasm-bindgen` CLI, which is never looked up on the
+/// system, but always installed (if needed) into the cache directory at the exact version pinned
+/// by the project's `Cargo.lock`. This is the version that the generated JS glue must match, or
+/// the application breaks at runtime.
+fn wasm_bindgen_status(cargo: &Cargo) -> Result<Tool> {
+    let Some(required) = WasmBindgen::find_version(cargo.workspace_dir().join("Cargo.lock")).ok()
+    else {
+        return Ok(Tool {
+            name: "wasm-bindgen",
+            status: FileStatus::Missing,
+            path: None,
+            version: None,
+        });
+    };
+
+    let bindgen = WasmBindgen::new(required.clone())?;
+    let (status, path) = if bindgen.installed() {
+        (FileStatus::Found, Some(bindgen.path().to_owned()))
+    } else {
+        (FileStatus::Missing, None)
+    };
+
+    Ok(Tool {
+        name: "wasm-bindgen",
+        status,
+        path,
+        version: Some(required.to_string()),
+    })
+}
+
+/// Run `<bin> --version` and extract the version from its output. Used for tools that print their
+/// version directly to stdout, like `sass` and `wasm-opt`.
+fn probe_flag_version(bin: &Path) -> Result<Option<String>> {
+    let output = Command::new(bin).arg("--version").output()?;
+    Ok(extract_version(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Run `<bin> --help` and extract the version from its output header. Used for `tailwindcss`,
+/// which prints its version as part of the `--help` banner instead of supporting `--version`.
+fn probe_help_version(bin: &Path) -> Result<Option<String>> {
+    let output = Command::new(bin).arg("--help").output()?;
+    Ok(extract_version(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Pull the first whitespace-separated token that looks like a version number (starting with a
+/// digit, optionally prefixed with `v`) out of a chunk of CLI output.
+fn extract_version(text: &str) -> Option<String> {
+    text.split_whitespace().find_map(|token| {
+        let token = token.trim_start_matches('v');
+        token.starts_with(|c: char| c.is_ascii_digit()).then(|| {
+            token
+                .trim_end_matches(|c: char| !c.is_ascii_digit())
+                .to_owned()
+        })
+    })
+}
+
+/// Compare a discovered tool version against the required/pinned one, ignoring a leading `v`
+/// prefix that some tools add.
+fn versions_match(discovered: &str, required: &str) -> bool {
+    discovered.trim_start_matches('v') == required.trim_start_matches('v')
 }
 
 /// Determine the status of a file within the current project.
@@ -170,4 +352,36 @@ mod tests {
         status(&std::env::current_dir()?.join("sample"))?;
         Ok(())
     }
+
+    #[test]
+    fn extract_version_plain() {
+        assert_eq!(Some("1.69.5".to_owned()), extract_version("1.69.5\n"));
+    }
+
+    #[test]
+    fn extract_version_with_prefix() {
+        assert_eq!(
+            Some("3.4.1".to_owned()),
+            extract_version("≈ tailwindcss v3.4.1\n\nUsage:\n")
+        );
+    }
+
+    #[test]
+    fn extract_version_plain_number() {
+        assert_eq!(
+            Some("116".to_owned()),
+            extract_version("wasm-opt version 116 (version_116)")
+        );
+    }
+
+    #[test]
+    fn extract_version_none_found() {
+        assert_eq!(None, extract_version("no version info here"));
+    }
+
+    #[test]
+    fn versions_match_ignores_v_prefix() {
+        assert!(versions_match("v1.2.3", "1.2.3"));
+        assert!(!versions_match("1.2.3", "1.2.4"));
+    }
 }
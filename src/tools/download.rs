@@ -0,0 +1,238 @@
+//! Shared machinery to download and extract pinned versions of external tools into the
+//! application's cache directory, mirroring what [`super::WasmBindgen`] already does for its own
+//! binary.
+
+use std::{
+    fs,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+};
+
+use color_eyre::eyre::{bail, eyre, Result};
+use directories::ProjectDirs;
+
+/// Archive format a release asset is packaged in.
+pub enum Archive {
+    /// A `.tar.gz` archive, as used by dart-sass and binaryen.
+    TarGz,
+    /// A `.zip` archive, as used by dart-sass on Windows.
+    Zip,
+    /// A plain, unpacked binary, as used by the tailwindcss standalone releases.
+    Raw,
+}
+
+/// Directory that holds the cached binaries of a single tool, one sub-folder per version.
+pub fn tool_cache_dir(tool: &str) -> Result<PathBuf> {
+    Ok(ProjectDirs::from("rocks", "dnaka91", "wazzup")
+        .ok_or_else(|| eyre!("failed finding project dirs"))?
+        .cache_dir()
+        .join(tool))
+}
+
+/// Download the asset at `url` and extract the single binary found at `member` (its path within
+/// the archive, ignoring any leading version-named directory) to `dest`. For [`Archive::Raw`],
+/// `member` is ignored and the downloaded bytes are written to `dest` directly.
+pub fn download_and_extract(url: &str, archive: Archive, member: &str, dest: &Path) -> Result<()> {
+    let bytes = download(url)?;
+
+    if let Some(dir) = dest.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    match archive {
+        Archive::TarGz => extract_tar_gz(&bytes, member, dest)?,
+        Archive::Zip => extract_zip(&bytes, member, dest)?,
+        Archive::Raw => fs::write(dest, bytes)?,
+    }
+
+    set_executable(dest)?;
+
+    Ok(())
+}
+
+/// Download the asset at `url` and extract the whole archive into `dest_dir`, stripping the single
+/// leading directory most release archives wrap their contents in. Used for tools like dart-sass,
+/// whose launcher script execs a sibling Dart runtime and snapshot file instead of being a
+/// self-contained binary, so extracting a single member out of the archive (as
+/// [`download_and_extract`] does) isn't enough to get a working install.
+///
+/// Permission bits are preserved from the archive, so executables stay executable.
+pub fn download_and_extract_dir(url: &str, archive: Archive, dest_dir: &Path) -> Result<()> {
+    let bytes = download(url)?;
+
+    fs::create_dir_all(dest_dir)?;
+
+    match archive {
+        Archive::TarGz => extract_tar_gz_dir(&bytes, dest_dir)?,
+        Archive::Zip => extract_zip_dir(&bytes, dest_dir)?,
+        Archive::Raw => bail!("raw archives can't be extracted as a directory"),
+    }
+
+    Ok(())
+}
+
+fn download(url: &str) -> Result<Vec<u8>> {
+    let resp = ureq::get(url).call()?;
+    let mut bytes = Vec::new();
+    resp.into_reader().read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn extract_tar_gz(bytes: &[u8], member: &str, dest: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(Cursor::new(bytes)));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+
+        if path.ends_with(member) {
+            let mut out = fs::File::create(dest)?;
+            std::io::copy(&mut entry, &mut out)?;
+            return Ok(());
+        }
+    }
+
+    bail!("no `{member}` entry found in downloaded archive");
+}
+
+fn extract_zip(bytes: &[u8], member: &str, dest: &Path) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let path = match entry.enclosed_name() {
+            Some(path) => path.to_owned(),
+            None => continue,
+        };
+
+        if path.ends_with(member) {
+            let mut out = fs::File::create(dest)?;
+            std::io::copy(&mut entry, &mut out)?;
+            return Ok(());
+        }
+    }
+
+    bail!("no `{member}` entry found in downloaded archive");
+}
+
+/// Extract every file entry of the archive into `dest_dir`, stripping the leading path component
+/// (the version-named directory the archive wraps its contents in), preserving unix permission
+/// bits so any embedded executables (the launcher script, the Dart runtime it execs) stay
+/// executable.
+fn extract_tar_gz_dir(bytes: &[u8], dest_dir: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(flate2::read::GzDecoder::new(Cursor::new(bytes)));
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+
+        let path = entry.path()?.into_owned();
+        let rel_path: PathBuf = path.components().skip(1).collect();
+
+        if rel_path.as_os_str().is_empty() {
+            continue;
+        }
+
+        let out = dest_dir.join(&rel_path);
+
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::File::create(&out)?;
+        std::io::copy(&mut entry, &mut file)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            fs::set_permissions(&out, fs::Permissions::from_mode(entry.header().mode()?))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// See [`extract_tar_gz_dir`]. Only used for the Windows dart-sass release, which is a `.zip`
+/// archive; Windows has no equivalent of the unix executable permission bit, so none is set here.
+fn extract_zip_dir(bytes: &[u8], dest_dir: &Path) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+
+        if entry.is_dir() {
+            continue;
+        }
+
+        let Some(path) = entry.enclosed_name() else {
+            continue;
+        };
+        let rel_path: PathBuf = path.components().skip(1).collect();
+
+        if rel_path.as_os_str().is_empty() {
+            continue;
+        }
+
+        let out = dest_dir.join(&rel_path);
+
+        if let Some(parent) = out.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::File::create(&out)?;
+        std::io::copy(&mut entry, &mut file)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut perm = fs::metadata(path)?.permissions();
+    let mode = perm.mode();
+
+    if mode & 0o100 == 0 {
+        perm.set_mode(mode | 0o100);
+        fs::set_permissions(path, perm)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// The Rust-style target triple of the host platform, as used to pick the right
+/// `wasm-bindgen` release asset. Linux x86_64 is special-cased to the `musl` triple, since that's
+/// the only one `rustwasm/wasm-bindgen` publishes prebuilt binaries for on that platform (the
+/// `gnu` triple 404s).
+pub fn host_triple() -> Result<&'static str> {
+    Ok(match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("linux", "x86_64") => "x86_64-unknown-linux-musl",
+        ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+        ("windows", "aarch64") => "aarch64-pc-windows-msvc",
+        (os, arch) => bail!("unsupported host platform `{arch}-{os}`"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_triple_is_known() {
+        // Just make sure this doesn't fail on the platforms we run CI on.
+        host_triple().unwrap();
+    }
+}
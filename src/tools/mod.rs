@@ -0,0 +1,903 @@
+//! Management and invocation of external tools, that are required to build projects.
+
+mod download;
+
+use std::{
+    ffi::OsString,
+    fs, iter,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use cargo_lock::Lockfile;
+use color_eyre::{
+    eyre::{bail, eyre, Result, WrapErr},
+    Help, SectionExt,
+};
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+use tracing::trace;
+
+/// Wrapper around [rustup](https://rustup.rs/), to manage toolchain and target installations.
+pub struct Rustup {}
+
+impl Rustup {
+    const WASM_TARGET: &'static str = "wasm32-unknown-unknown";
+
+    fn bin_path() -> Result<&'static Path> {
+        static BIN_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+        BIN_PATH
+            .get_or_try_init(|| find_bin("rustup"))
+            .map(PathBuf::as_path)
+    }
+
+    pub fn check_wasm_target() -> Result<bool> {
+        let output = Command::new(Self::bin_path()?)
+            .args(["target", "list", "--installed"])
+            .output()?;
+
+        if !output.status.success() {
+            bail!(
+                "failed checking installed targets: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        let stdout = String::from_utf8(output.stdout)?;
+
+        Ok(stdout.lines().any(|line| line == Self::WASM_TARGET))
+    }
+
+    pub fn install_wasm_target() -> Result<()> {
+        let output = Command::new(Self::bin_path()?)
+            .args(["target", "add", Self::WASM_TARGET])
+            .output()?;
+
+        if !output.status.success() {
+            bail!(
+                "failed installing wasm target: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Wrapper around [cargo](https://doc.rust-lang.org/cargo), to compile the Rust code into WASM
+/// binaries.
+pub struct Cargo {
+    /// Location of the workspace root, which can be the project path itself if it's at the top.
+    workspace_dir: PathBuf,
+    /// Location of the `target` directly usually located at the workspace root. May be changed by
+    /// user configuration.
+    target_dir: PathBuf,
+    /// Crate names of every buildable `bin` target in the workspace.
+    bin_targets: Vec<String>,
+}
+
+impl Cargo {
+    const WASM_TARGET: &'static str = "wasm32-unknown-unknown";
+
+    fn bin_path() -> Result<&'static Path> {
+        static BIN_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+        BIN_PATH
+            .get_or_try_init(|| find_bin("cargo"))
+            .map(PathBuf::as_path)
+    }
+
+    /// Create a new instance for the given project. This will directly locate the workspace root,
+    /// target directory and the `bin` targets to build, for later use.
+    pub fn new(working_dir: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Metadata {
+            target_directory: PathBuf,
+            workspace_root: PathBuf,
+            workspace_members: Vec<String>,
+            packages: Vec<Package>,
+        }
+
+        #[derive(Deserialize)]
+        struct Package {
+            id: String,
+            targets: Vec<Target>,
+        }
+
+        #[derive(Deserialize)]
+        struct Target {
+            name: String,
+            kind: Vec<String>,
+        }
+
+        let mut cmd = Command::new(Self::bin_path()?);
+        cmd.current_dir(working_dir);
+        cmd.args(["metadata", "--format-version", "1"]);
+
+        trace!(?cmd, "invoking cargo");
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(eyre!("cargo exited with non-zero status code"))
+                .with_section(move || format!("{cmd:?}").header("Command:"))
+                .with_section(move || stderr.trim().to_string().header("Stderr:"))?;
+        }
+
+        let mut deser = serde_json::Deserializer::from_slice(&output.stdout);
+        let Metadata {
+            target_directory,
+            workspace_root,
+            workspace_members,
+            packages,
+        } = serde_path_to_error::deserialize::<_, Metadata>(&mut deser)
+            .wrap_err("failed parsing Cargo metadata")?;
+
+        let bin_targets = packages
+            .into_iter()
+            .filter(|package| workspace_members.contains(&package.id))
+            .flat_map(|package| package.targets)
+            .filter(|target| target.kind.iter().any(|kind| kind == "bin"))
+            .map(|target| target.name)
+            .collect();
+
+        Ok(Self {
+            workspace_dir: workspace_root,
+            target_dir: target_directory,
+            bin_targets,
+        })
+    }
+
+    pub fn run(&self, working_dir: &Path, release: bool, profile: &str) -> Result<()> {
+        let mut cmd = Command::new(Self::bin_path()?);
+        cmd.current_dir(working_dir);
+        cmd.args([
+            "build",
+            "--color",
+            "always",
+            "--target",
+            Self::WASM_TARGET,
+            "--target-dir",
+        ]);
+        cmd.arg(self.target_dir.join("wazzup"));
+
+        if release {
+            cmd.args(["--profile", profile]);
+        }
+
+        trace!(?cmd, "invoking cargo");
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(eyre!("cargo exited with non-zero status code"))
+                .with_section(move || format!("{cmd:?}").header("Command:"))
+                .with_section(move || stderr.trim().to_string().header("Stderr:"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the directory of the workspace root, which is where most mandatory files are located
+    /// (like the `Cargo.lock`).
+    pub fn workspace_dir(&self) -> &Path {
+        &self.workspace_dir
+    }
+
+    /// Crate names of every buildable `bin` target in the workspace.
+    pub fn bin_targets(&self) -> &[String] {
+        &self.bin_targets
+    }
+
+    /// Output directory for compilation artifacts.
+    pub fn target_dir(&self) -> &Path {
+        &self.target_dir
+    }
+}
+
+/// Wrapper around [wasm-bindgen](https://rustwasm.github.io/docs/wasm-bindgen/), to generate
+/// needed JavaScript glue to for loading in the browser.
+pub struct WasmBindgen {
+    /// Version of `wasm-bindgen`, as discovered from the project's `Cargo.lock` file.
+    version: semver::Version,
+    /// Absolute path to the binary.
+    path: PathBuf,
+}
+
+impl WasmBindgen {
+    /// Find the `wasm-bingen` version from a project's Cargo.lock file.
+    pub fn find_version(lockfile: impl AsRef<Path>) -> Result<semver::Version> {
+        Ok(Lockfile::load(lockfile)?
+            .packages
+            .into_iter()
+            .find(|p| p.name.as_str() == "wasm-bindgen")
+            .ok_or_else(|| eyre!("no wasm-bindgen dependency"))?
+            .version)
+    }
+
+    /// Create a new instance for the specific version of `wasm-bindgen`. This binary for this
+    /// version may or may not exist on the system.
+    pub fn new(version: semver::Version) -> Result<Self> {
+        let path = download::tool_cache_dir("wasm-bindgen")?
+            .join(version.to_string())
+            .join("wasm-bindgen");
+
+        Ok(Self { version, path })
+    }
+
+    /// Check whether the current version of `wasm-bindgen` is locally installed.
+    pub fn installed(&self) -> bool {
+        self.path.exists()
+    }
+
+    /// Absolute path to the binary, whether or not it is currently installed.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Installed the version of `wasm-bindgen` as represented by this instance.
+    ///
+    /// This prefers downloading the prebuilt binary from the project's GitHub releases, which is
+    /// much faster than compiling it from scratch. If no prebuilt release exists for the host
+    /// platform, this falls back to building it with `cargo install` instead.
+    pub fn install(&self) -> Result<()> {
+        if let Err(e) = self.install_prebuilt() {
+            trace!(error = ?e, "no prebuilt wasm-bindgen available, building from source instead");
+            self.install_from_source()?;
+        }
+
+        Ok(())
+    }
+
+    /// Download the prebuilt `wasm-bindgen` binary for the host platform from the `rustwasm/
+    /// wasm-bindgen` GitHub releases.
+    fn install_prebuilt(&self) -> Result<()> {
+        let triple = download::host_triple()?;
+        let version = &self.version;
+        let url = format!(
+            "https://github.com/rustwasm/wasm-bindgen/releases/download/{version}/\
+             wasm-bindgen-{version}-{triple}.tar.gz"
+        );
+
+        download::download_and_extract(&url, download::Archive::TarGz, "wasm-bindgen", &self.path)
+    }
+
+    /// Build `wasm-bindgen` from source with `cargo install`, into a temporary directory, and then
+    /// copy it over to the application's cache folder. That allows to have multiple versions
+    /// installed for re-use, and not interefere with the potentially system-installed version.
+    fn install_from_source(&self) -> Result<()> {
+        let tempdir = tempfile::tempdir()?;
+
+        let output = Command::new(Cargo::bin_path()?)
+            .args(["install", "--root"])
+            .arg(tempdir.path())
+            .args([
+                "--no-track",
+                "--bin",
+                "wasm-bindgen",
+                "--version",
+                &self.version.to_string(),
+                "wasm-bindgen-cli",
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            bail!(
+                "failed building wasm-bindgen (v{}): {}",
+                self.version,
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::copy(tempdir.path().join("bin/wasm-bindgen"), &self.path)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perm = fs::metadata(&self.path)?.permissions();
+            let mode = perm.mode();
+
+            if mode & 0o100 == 0 {
+                perm.set_mode(mode | 0o100);
+                fs::set_permissions(&self.path, perm)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn run(&self, target: &Path, out: &Path) -> Result<()> {
+        let mut cmd = Command::new(&self.path);
+
+        cmd.args([
+            "--target",
+            "web",
+            "--no-typescript",
+            "--omit-default-module-path",
+            "--out-dir",
+        ]);
+        cmd.args([out, target]);
+
+        trace!(?cmd, "invoking wasm-bindgen");
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(eyre!("wasm-bindgen exited with non-zero status code"))
+                .with_section(move || format!("{cmd:?}").header("Command:"))
+                .with_section(move || stderr.trim().to_string().header("Stderr:"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Wrapper around [wasm-opt](https://github.com/WebAssembly/binaryen), to further optimize WASM
+/// binaries for speed or size.
+pub struct WasmOpt {
+    bin_path: PathBuf,
+}
+
+impl WasmOpt {
+    /// Create a new instance, using the pinned `version` if given (downloading it into the cache
+    /// directory on first use), or falling back to a system-wide install otherwise.
+    pub fn new(pinned: Option<&str>) -> Result<Self> {
+        match pinned {
+            Some(version) => Self::pinned(version),
+            None => find_bin("wasm-opt").map(|bin_path| Self { bin_path }),
+        }
+    }
+
+    /// Path the `wasm-opt` binary for the given pinned version would be installed at, whether or
+    /// not it actually is yet. Used by [`crate::status`] to probe the binary that a build would
+    /// actually use, instead of whatever (if anything) is installed system-wide.
+    pub fn cache_path(version: &str) -> Result<PathBuf> {
+        Ok(download::tool_cache_dir("wasm-opt")?.join(version).join(
+            if cfg!(windows) {
+                "wasm-opt.exe"
+            } else {
+                "wasm-opt"
+            },
+        ))
+    }
+
+    fn pinned(version: &str) -> Result<Self> {
+        let bin_path = Self::cache_path(version)?;
+
+        if !bin_path.exists() {
+            Self::install(version, &bin_path)?;
+        }
+
+        Ok(Self { bin_path })
+    }
+
+    /// Download and extract the `wasm-opt` binary bundled in a `binaryen` release, for the given
+    /// version, into `dest`.
+    fn install(version: &str, dest: &Path) -> Result<()> {
+        let triple = if cfg!(target_os = "windows") {
+            "x86_64-windows"
+        } else if cfg!(target_os = "macos") {
+            if cfg!(target_arch = "aarch64") {
+                "arm64-macos"
+            } else {
+                "x86_64-macos"
+            }
+        } else if cfg!(target_arch = "aarch64") {
+            "aarch64-linux"
+        } else {
+            "x86_64-linux"
+        };
+
+        let url = format!(
+            "https://github.com/WebAssembly/binaryen/releases/download/version_{version}/\
+             binaryen-version_{version}-{triple}.tar.gz"
+        );
+        let member = if cfg!(windows) {
+            "wasm-opt.exe"
+        } else {
+            "wasm-opt"
+        };
+
+        download::download_and_extract(&url, download::Archive::TarGz, member, dest)
+    }
+
+    pub fn run(&self, target: &Path, settings: &WasmOptSettings) -> Result<()> {
+        let mut cmd = Command::new(&self.bin_path);
+
+        cmd.arg(format!("-O{}", settings.level));
+        cmd.args(&settings.passes);
+
+        if settings.strip_debug {
+            cmd.arg("--strip-debug");
+        }
+        if settings.strip_producers {
+            cmd.arg("--strip-producers");
+        }
+
+        cmd.arg("--output");
+        cmd.args([target, target]);
+
+        trace!(?cmd, level = %settings.level, "invoking wasm-opt");
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(eyre!("wasm-opt exited with non-zero status code"))
+                .with_section(move || format!("{cmd:?}").header("Command:"))
+                .with_section(move || stderr.trim().to_string().header("Stderr:"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Wrapper around [dart-sass](https://github.com/sass/dart-sass), to compile SASS/SCSS/CSS files
+/// into optimized CSS stylesheets.
+pub struct Sass {
+    bin_path: PathBuf,
+}
+
+impl Sass {
+    /// Create a new instance, using the pinned `version` if given (downloading it into the cache
+    /// directory on first use), or falling back to the first `sass` found on the system otherwise.
+    pub fn new(root: &Path, project: &Path, pinned: Option<&str>) -> Result<Self> {
+        match pinned {
+            Some(version) => Self::pinned(version),
+            None => find_bin_js("sass", root, project).map(|bin_path| Self { bin_path }),
+        }
+    }
+
+    /// Directory the given pinned version would be extracted into, whether or not it actually has
+    /// been yet. The `sass`/`sass.bat` launcher within it is given by [`Self::cache_path`].
+    fn cache_dir(version: &str) -> Result<PathBuf> {
+        Ok(download::tool_cache_dir("sass")?.join(version))
+    }
+
+    /// Path the `sass` launcher for the given pinned version would be installed at, whether or not
+    /// it actually is yet. Used by [`crate::status`] to probe the binary that a build would
+    /// actually use, instead of whatever (if anything) is installed system-wide.
+    pub fn cache_path(version: &str) -> Result<PathBuf> {
+        Ok(Self::cache_dir(version)?.join(if cfg!(windows) { "sass.bat" } else { "sass" }))
+    }
+
+    fn pinned(version: &str) -> Result<Self> {
+        let dir = Self::cache_dir(version)?;
+        let bin_path = Self::cache_path(version)?;
+
+        if !bin_path.exists() {
+            Self::install(version, &dir)?;
+        }
+
+        Ok(Self { bin_path })
+    }
+
+    /// Download and extract a full `dart-sass` release, for the given version, into `dir`. The
+    /// release isn't a self-contained binary: its launcher script (`sass`/`sass.bat`) execs a
+    /// sibling Dart runtime and snapshot file under `src/`, so the whole archive has to be
+    /// extracted rather than just the launcher.
+    fn install(version: &str, dir: &Path) -> Result<()> {
+        let (os, ext, archive) = if cfg!(target_os = "windows") {
+            ("windows", "zip", download::Archive::Zip)
+        } else if cfg!(target_os = "macos") {
+            ("macos", "tar.gz", download::Archive::TarGz)
+        } else {
+            ("linux", "tar.gz", download::Archive::TarGz)
+        };
+        let arch = if cfg!(target_arch = "aarch64") {
+            "arm64"
+        } else {
+            "x64"
+        };
+
+        let url = format!(
+            "https://github.com/sass/dart-sass/releases/download/{version}/\
+             dart-sass-{version}-{os}-{arch}.{ext}"
+        );
+
+        download::download_and_extract_dir(&url, archive, dir)
+    }
+
+    pub fn run(&self, target: &Path, out: &Path, release: bool) -> Result<()> {
+        let mut cmd = Command::new(&self.bin_path);
+
+        cmd.arg("--no-source-map");
+        cmd.args([target, out]);
+
+        if release {
+            cmd.args(["--style", "compressed"]);
+        }
+
+        trace!(?cmd, "invoking sass");
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(eyre!("sass exited with non-zero status code"))
+                .with_section(move || format!("{cmd:?}").header("Command:"))
+                .with_section(move || stderr.trim().to_string().header("Stderr:"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Wrapper around [tailwind](https://github.com/tailwindlabs/tailwindcss), to generate
+/// `TailwindCSS` stylesheets based on the project source files.
+pub struct Tailwind {
+    bin_path: PathBuf,
+}
+
+impl Tailwind {
+    /// Create a new instance, using the pinned `version` if given (downloading it into the cache
+    /// directory on first use), or falling back to the first `tailwindcss` found on the system
+    /// otherwise.
+    pub fn new(root: &Path, project: &Path, pinned: Option<&str>) -> Result<Self> {
+        match pinned {
+            Some(version) => Self::pinned(version),
+            None => find_bin_js("tailwindcss", root, project).map(|bin_path| Self { bin_path }),
+        }
+    }
+
+    /// Path the `tailwindcss` binary for the given pinned version would be installed at, whether
+    /// or not it actually is yet. Used by [`crate::status`] to probe the binary that a build would
+    /// actually use, instead of whatever (if anything) is installed system-wide.
+    pub fn cache_path(version: &str) -> Result<PathBuf> {
+        Ok(download::tool_cache_dir("tailwindcss")?.join(version).join(
+            if cfg!(windows) {
+                "tailwindcss.exe"
+            } else {
+                "tailwindcss"
+            },
+        ))
+    }
+
+    fn pinned(version: &str) -> Result<Self> {
+        let bin_path = Self::cache_path(version)?;
+
+        if !bin_path.exists() {
+            Self::install(version, &bin_path)?;
+        }
+
+        Ok(Self { bin_path })
+    }
+
+    /// Download the standalone `tailwindcss` release binary for the given version into `dest`.
+    fn install(version: &str, dest: &Path) -> Result<()> {
+        let os = if cfg!(target_os = "windows") {
+            "windows"
+        } else if cfg!(target_os = "macos") {
+            "macos"
+        } else {
+            "linux"
+        };
+        let arch = if cfg!(target_arch = "aarch64") {
+            "arm64"
+        } else {
+            "x64"
+        };
+        let ext = if cfg!(windows) { ".exe" } else { "" };
+
+        let url = format!(
+            "https://github.com/tailwindlabs/tailwindcss/releases/download/v{version}/\
+             tailwindcss-{os}-{arch}{ext}"
+        );
+
+        download::download_and_extract(&url, download::Archive::Raw, "", dest)
+    }
+
+    pub fn run(&self, target: &Path, out: &Path, release: bool) -> Result<()> {
+        let mut cmd = Command::new(&self.bin_path);
+
+        cmd.arg("--input").arg(target);
+        cmd.arg("--output").arg(out);
+
+        if release {
+            cmd.arg("--minify");
+        }
+
+        trace!(?cmd, "invoking tailwindcss");
+        let output = cmd.output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(eyre!("tailwindcss exited with non-zero status code"))
+                .with_section(move || format!("{cmd:?}").header("Command:"))
+                .with_section(move || stderr.trim().to_string().header("Stderr:"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Versions of `sass`, `tailwindcss` and `wasm-opt` to download and pin, configured through a
+/// `[package.metadata.wazzup.tools]` table in the project's `Cargo.toml`, similar to Trunk's
+/// `[tools]` config block.
+///
+/// Any tool left unset here falls back to searching the system (and `node_modules/.bin` for the
+/// JS-based tools) as before.
+#[derive(Debug, Default, Deserialize)]
+#[cfg_attr(test, derive(Eq, PartialEq))]
+pub struct ToolVersions {
+    pub sass: Option<String>,
+    pub tailwindcss: Option<String>,
+    pub wasm_opt: Option<String>,
+}
+
+impl ToolVersions {
+    /// Load the pinned tool versions from the project's `Cargo.toml`. Returns the default (no
+    /// pinned versions) if the `[package.metadata.wazzup.tools]` table is absent.
+    pub fn load(project: &Path) -> Result<Self> {
+        #[derive(Default, Deserialize)]
+        struct CargoToml {
+            #[serde(default)]
+            package: Package,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Package {
+            #[serde(default)]
+            metadata: Metadata,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Metadata {
+            #[serde(default)]
+            wazzup: Wazzup,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Wazzup {
+            #[serde(default)]
+            tools: ToolVersions,
+        }
+
+        let buf = fs::read_to_string(project.join("Cargo.toml"))
+            .wrap_err("failed to read the Cargo.toml manifest")?;
+
+        let deser = toml::Deserializer::new(&buf);
+        serde_path_to_error::deserialize::<_, CargoToml>(deser)
+            .wrap_err("failed to parse the Cargo.toml manifest")
+            .map(|toml| toml.package.metadata.wazzup.tools)
+    }
+}
+
+/// Optimization strategy for [`WasmOpt::run`], configured through a
+/// `[package.metadata.wazzup.wasm_opt]` table in the project's `Cargo.toml`.
+///
+/// Defaults to `-O4` (optimize for speed), which matches the previous, hardcoded behavior.
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(test, derive(Eq, PartialEq))]
+#[serde(default)]
+pub struct WasmOptSettings {
+    /// Optimization level passed as `-O<level>`, for example `4` (speed, the default), `s` or `z`
+    /// (increasingly aggressive size optimizations).
+    pub level: String,
+    /// Additional pass names to run, appended as-is after the optimization level (for example
+    /// `--dce` or `--vacuum`).
+    pub passes: Vec<String>,
+    /// Strip debug info from the binary via `--strip-debug`.
+    pub strip_debug: bool,
+    /// Strip the `producers` custom section from the binary via `--strip-producers`.
+    pub strip_producers: bool,
+}
+
+impl Default for WasmOptSettings {
+    fn default() -> Self {
+        Self {
+            level: "4".to_owned(),
+            passes: Vec::new(),
+            strip_debug: false,
+            strip_producers: false,
+        }
+    }
+}
+
+impl WasmOptSettings {
+    /// Load the optimization settings from the project's `Cargo.toml`. Returns the default
+    /// (`-O4`, no extra passes or stripping) if the `[package.metadata.wazzup.wasm_opt]` table is
+    /// absent.
+    pub fn load(project: &Path) -> Result<Self> {
+        #[derive(Default, Deserialize)]
+        struct CargoToml {
+            #[serde(default)]
+            package: Package,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Package {
+            #[serde(default)]
+            metadata: Metadata,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Metadata {
+            #[serde(default)]
+            wazzup: Wazzup,
+        }
+
+        #[derive(Default, Deserialize)]
+        struct Wazzup {
+            #[serde(default)]
+            wasm_opt: WasmOptSettings,
+        }
+
+        let buf = fs::read_to_string(project.join("Cargo.toml"))
+            .wrap_err("failed to read the Cargo.toml manifest")?;
+
+        let deser = toml::Deserializer::new(&buf);
+        serde_path_to_error::deserialize::<_, CargoToml>(deser)
+            .wrap_err("failed to parse the Cargo.toml manifest")
+            .map(|toml| toml.package.metadata.wazzup.wasm_opt)
+    }
+}
+
+pub fn find_bin(name: &str) -> Result<PathBuf> {
+    which::which_global(name).wrap_err_with(|| {
+        format!(
+            "missing `{name}` binary, try to install it through your OS package manager and make \
+             sure it's available through the PATH env variable"
+        )
+    })
+}
+
+pub fn find_bin_js(name: &str, root: &Path, cwd: &Path) -> Result<PathBuf> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    let paths = cwd
+        .ancestors()
+        .filter(|&path| path.starts_with(root))
+        .map(|path| path.join("node_modules/.bin"))
+        .chain(iter::once(path.into()))
+        .fold(OsString::new(), |mut acc, path| {
+            if !acc.is_empty() {
+                acc.push(":");
+            }
+
+            acc.push(path);
+            acc
+        });
+
+    which::which_in(name, Some(paths), cwd).wrap_err(format!(
+        "missing `{name}` binary, try to install it through your OS package manager and make sure \
+         it's available through the PATH env variable"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rustup_check_wasm_target() -> Result<()> {
+        assert!(Rustup::check_wasm_target()?);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(coverage))]
+    fn run_cargo_bindgen_opt() -> Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let project = dir.path().join("temp");
+
+        let status = Command::new(Cargo::bin_path()?)
+            .current_dir(dir.path())
+            .args(["new", "temp"])
+            .output()?
+            .status;
+        assert!(status.success());
+
+        let status = Command::new(Cargo::bin_path()?)
+            .current_dir(&project)
+            .args(["add", "wasm-bindgen"])
+            .output()?
+            .status;
+        assert!(status.success());
+
+        Cargo::new(&project)?.run(&project, false, "release")?;
+
+        let bindgen = WasmBindgen::new(WasmBindgen::find_version(project.join("Cargo.lock"))?)?;
+        if !bindgen.installed() {
+            bindgen.install()?;
+        }
+
+        bindgen.run(
+            &project.join("target/wazzup/wasm32-unknown-unknown/debug/temp.wasm"),
+            &project.join("dist"),
+        )?;
+
+        WasmOpt::new(None)?.run(
+            &project.join("dist/temp_bg.wasm"),
+            &WasmOptSettings::default(),
+        )?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn tool_versions_default_when_unset() -> Result<()> {
+        let dir = assert_fs::TempDir::new()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"\nversion = \"0.1.0\"\n",
+        )?;
+
+        assert_eq!(ToolVersions::default(), ToolVersions::load(dir.path())?);
+        Ok(())
+    }
+
+    #[test]
+    fn tool_versions_parsed_from_metadata() -> Result<()> {
+        let dir = assert_fs::TempDir::new()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"
+            [package]
+            name = "test"
+            version = "0.1.0"
+
+            [package.metadata.wazzup.tools]
+            sass = "1.69.5"
+            tailwindcss = "3.4.1"
+            "#,
+        )?;
+
+        assert_eq!(
+            ToolVersions {
+                sass: Some("1.69.5".to_owned()),
+                tailwindcss: Some("3.4.1".to_owned()),
+                wasm_opt: None,
+            },
+            ToolVersions::load(dir.path())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wasm_opt_settings_default_when_unset() -> Result<()> {
+        let dir = assert_fs::TempDir::new()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"\nversion = \"0.1.0\"\n",
+        )?;
+
+        assert_eq!(
+            WasmOptSettings::default(),
+            WasmOptSettings::load(dir.path())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn wasm_opt_settings_parsed_from_metadata() -> Result<()> {
+        let dir = assert_fs::TempDir::new()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            r#"
+            [package]
+            name = "test"
+            version = "0.1.0"
+
+            [package.metadata.wazzup.wasm_opt]
+            level = "z"
+            passes = ["--dce"]
+            strip_debug = true
+            strip_producers = true
+            "#,
+        )?;
+
+        assert_eq!(
+            WasmOptSettings {
+                level: "z".to_owned(),
+                passes: vec!["--dce".to_owned()],
+                strip_debug: true,
+                strip_producers: true,
+            },
+            WasmOptSettings::load(dir.path())?
+        );
+        Ok(())
+    }
+}
@@ -1,12 +1,12 @@
 //! Debouncing logic for [`watcher`](super::watcher) events, as these can occur very frequently.
 //!
-//! This _throttles_ said events. If an event is received, it is first put into a hash map,
-//! together with the timestamp of the arrival. Any event of the same type that is received, in the
-//! meantime, is simply dropped.
-//!
-//! On a regular basis, the hash map is checked for any "expired" events, meaning events that
-//! passed a time threshold from the point they were received until now. These are taken out of the
-//! map and send over a channel to the receiver.
+//! Every incoming [`ChangeType`] is buffered in a hash map, keyed by the change itself (so e.g. two
+//! `Static` events for different paths are tracked separately), together with the timestamp of its
+//! most recent occurrence. Any further event of the same kind arriving before the debounce period
+//! elapses simply refreshes that timestamp, so a steady stream of saves (or a `git checkout`
+//! touching hundreds of files) keeps pushing the flush out instead of triggering a rebuild per
+//! event. Only once a kind has been quiet for the configured duration is it flushed over the
+//! channel to the receiver, collapsing the whole burst into a single change.
 
 use std::{
     collections::{HashMap, HashSet},
@@ -65,9 +65,9 @@ pub fn debounce(watcher: watcher::Handle, debounce: Duration) -> Result<Handle>
                 .wait_timeout(Duration::from_millis(500));
 
             match res {
-                // Got new FS event, just store it
+                // Got new FS event, (re-)starting its quiet period
                 Ok(Some(change)) => {
-                    debouncer.changes.entry(change).or_insert_with(Instant::now);
+                    debouncer.changes.insert(change, Instant::now());
                 }
                 // Shutdown signal, or event channel closed
                 Ok(None) => break,
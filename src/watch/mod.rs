@@ -20,6 +20,7 @@ mod watcher;
 use std::{
     fmt::{self, Display},
     path::PathBuf,
+    time::Duration,
 };
 
 pub use debouncer::debounce;
@@ -28,6 +29,35 @@ pub use watcher::watch;
 /// Size for any message channels used within the watcher and debouncer.
 const CHANNEL_SIZE: usize = 16;
 
+/// Strategy used to register file system watches for a project.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, clap::ValueEnum)]
+pub enum WatchMode {
+    /// Register a single recursive watch per top-level directory, letting the OS/[`notify`]
+    /// backend track newly created sub-directories itself. Uses far fewer watch descriptors than
+    /// [`WatchMode::NonRecursive`] and should be preferred on large projects, as it avoids
+    /// exhausting `inotify`'s `max_user_watches` limit on Linux.
+    Recursive,
+    /// Register a dedicated, non-recursive watch for every directory in the project individually,
+    /// re-registering as directories are created or removed. Kept as a fallback for backends where
+    /// recursive watches misbehave.
+    NonRecursive,
+}
+
+/// Which [`notify`] backend to drive the watcher with.
+///
+/// The native backend relies on kernel-level notifications (inotify, FSEvents,
+/// `ReadDirectoryChangesW`), which are unavailable on many network mounts, Docker bind mounts, and
+/// when crossing the WSL host/guest boundary, silently leaving `wazzup dev` without any rebuilds in
+/// those environments. The polling backend works everywhere at the cost of higher resource usage
+/// and up to one poll interval of latency.
+#[derive(Clone, Copy, Debug)]
+pub enum WatchBackend {
+    /// Kernel-level file system notifications, through `notify::recommended_watcher`.
+    Native,
+    /// Poll the file system for changes on the given interval, through `notify::PollWatcher`.
+    Polling(Duration),
+}
+
 /// Identifier for what part of the project was changed in the file system.
 ///
 /// This can then be used, to identify which part to rebuild, instead of building the whole project
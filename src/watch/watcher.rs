@@ -2,6 +2,7 @@
 //! based on the paths modified.
 
 use std::{
+    io,
     path::{Path, PathBuf},
     thread,
 };
@@ -18,7 +19,7 @@ use notify::{
 };
 use tracing::{debug, error, trace, warn};
 
-use super::ChangeType;
+use super::{ChangeType, WatchBackend, WatchMode};
 
 /// Background file watcher that handles raw file system events from the [`notify`] crate.
 ///
@@ -28,8 +29,18 @@ struct ProjectWatcher {
     /// Path to the project directory.
     project: PathBuf,
     /// The underlying [`notify`] watcher, needed to watch/unwatch, after initially building it.
-    watcher: notify::RecommendedWatcher,
-    /// Loaded gitignore patterns, to filter out any folders or files added later on.
+    ///
+    /// Boxed as a trait object so the caller of [`watch`] can pick between the native,
+    /// kernel-notification-backed watcher and a polling one, with the rest of the event loop none
+    /// the wiser.
+    watcher: Box<dyn Watcher + Send>,
+    /// Builder accumulating every `.gitignore`/`.ignore` file found so far, kept around so newly
+    /// discovered ignore files can be folded in without starting over from scratch.
+    gitignore_builder: GitignoreBuilder,
+    /// Compiled patterns from [`Self::gitignore_builder`], to filter out any folders or files added
+    /// later on. Rebuilt whenever a `.gitignore`/`.ignore` file is created or modified, so rules
+    /// from nested ignore files are applied to watch-time filtering as well (see
+    /// [`collect_gitignore`] for how they are matched).
     gitignore: Gitignore,
     /// Receiver for events from [`notify`].
     notify_rx: flume::Receiver<Result<Event, notify::Error>>,
@@ -38,6 +49,8 @@ struct ProjectWatcher {
     /// Listener for a shut down signal from the [`Handle`], which will halt the event loop and
     /// stop all watching machinery.
     shutdown: flume::Receiver<()>,
+    /// Strategy used for registering new watch paths, as chosen by the caller of [`watch`].
+    mode: WatchMode,
 }
 
 impl ProjectWatcher {
@@ -60,9 +73,12 @@ impl ProjectWatcher {
             }
             EventKind::Create(_) => {
                 self.add_paths(&ev.paths);
+                self.refresh_gitignore(&ev.paths);
             }
             EventKind::Modify(modify) => match modify {
-                ModifyKind::Any | ModifyKind::Other | ModifyKind::Data(_) => {}
+                ModifyKind::Any | ModifyKind::Other | ModifyKind::Data(_) => {
+                    self.refresh_gitignore(&ev.paths);
+                }
                 ModifyKind::Metadata(_) => {
                     // metadata changes aren't important to us
                     return;
@@ -130,6 +146,10 @@ impl ProjectWatcher {
 
     /// Add the paths to the file watcher, filtering out any that should be ignored by the
     /// `.gitignore` patterns.
+    ///
+    /// In [`WatchMode::Recursive`], only brand new top-level entries need registering here, as
+    /// anything nested beneath an already-watched top-level directory is picked up by the backend
+    /// on its own.
     fn add_paths(&mut self, paths: &[impl AsRef<Path>]) {
         for path in paths {
             let path = path.as_ref();
@@ -138,14 +158,58 @@ impl ProjectWatcher {
                 continue;
             }
 
-            if let Err(e) = self.watcher.watch(path, RecursiveMode::NonRecursive) {
+            let recursive_mode = match self.mode {
+                WatchMode::NonRecursive => RecursiveMode::NonRecursive,
+                WatchMode::Recursive if path.parent() == Some(self.project.as_path()) => {
+                    RecursiveMode::Recursive
+                }
+                WatchMode::Recursive => continue,
+            };
+
+            if let Err(e) = self.watcher.watch(path, recursive_mode) {
                 error!(error = %e, "failed adding path to watcher");
             }
         }
     }
 
+    /// Fold any newly created or modified `.gitignore`/`.ignore` file into the accumulated
+    /// [`GitignoreBuilder`] and recompile it, so rules added to a nested ignore file after start-up
+    /// take effect immediately, matched the same way as in [`collect_gitignore`].
+    fn refresh_gitignore(&mut self, paths: &[PathBuf]) {
+        let mut touched = false;
+
+        for path in paths {
+            if !is_ignore_file(path) {
+                continue;
+            }
+
+            if let Some(error) = self.gitignore_builder.add(path) {
+                warn!(error = %error, path = %path.display(), "failed reading ignore file");
+                continue;
+            }
+
+            touched = true;
+        }
+
+        if !touched {
+            return;
+        }
+
+        match self.gitignore_builder.build() {
+            Ok(gitignore) => self.gitignore = gitignore,
+            Err(e) => error!(error = %e, "failed rebuilding ignore patterns"),
+        }
+    }
+
     /// Remove the given paths from the watcher again.
+    ///
+    /// In [`WatchMode::Recursive`], the backend drops watches for removed directories on its own,
+    /// so there is nothing to do here.
     fn remove_paths(&mut self, paths: &[impl AsRef<Path>]) {
+        if self.mode == WatchMode::Recursive {
+            return;
+        }
+
         for path in paths {
             if let Err(e) = self.watcher.unwatch(path.as_ref()) {
                 warn!(error = %e, "failed removing path from watcher");
@@ -154,6 +218,79 @@ impl ProjectWatcher {
     }
 }
 
+/// Whether `path` is a file that ignore patterns are read from (`.gitignore` or `.ignore`), and
+/// should therefore trigger [`ProjectWatcher::refresh_gitignore`] when it changes.
+fn is_ignore_file(path: &Path) -> bool {
+    matches!(
+        path.file_name().and_then(|name| name.to_str()),
+        Some(".gitignore" | ".ignore")
+    )
+}
+
+/// Build the combined ignore patterns for `project`, covering the same files the [`ignore`] crate
+/// itself applies during a directory walk: the repo-wide `.git/info/exclude`, the root `.gitignore`
+/// and `.ignore`, and every nested `.gitignore`/`.ignore` found in sub-directories. All patterns go
+/// into a single matcher rooted at `project`, so a rule from a nested file is matched relative to
+/// the project root, not to the directory the file was found in (for example, `ignored.txt` in
+/// `a/.gitignore` also matches `b/ignored.txt`).
+///
+/// This is used for watch-time filtering (in [`ProjectWatcher::handle_event`] and
+/// [`ProjectWatcher::add_paths`]), instead of only looking at the project root's `.gitignore`.
+fn collect_gitignore(project: &Path) -> Result<GitignoreBuilder> {
+    let mut builder = GitignoreBuilder::new(project);
+    builder.add_line(None, ".git/")?;
+    // `build::AssetIndex` and `freshness::Cache` persist their state here; without this, every
+    // cache write would be seen as an untracked project change and trigger a spurious rebuild,
+    // regardless of whether the project's own `.gitignore` excludes it.
+    builder.add_line(None, ".wazzup-cache/")?;
+    // The line above only matches the directory itself; this one matches the files inside it.
+    builder.add_line(None, ".wazzup-cache/**")?;
+
+    add_ignore_file(&mut builder, project.join(".git/info/exclude"))?;
+    add_ignore_file(&mut builder, project.join(".gitignore"))?;
+    add_ignore_file(&mut builder, project.join(".ignore"))?;
+
+    let mut walker = WalkBuilder::new(project);
+    walker
+        .standard_filters(false)
+        .require_git(false)
+        .git_exclude(true)
+        .git_ignore(true);
+
+    for entry in walker.build().skip(1) {
+        let entry = entry?;
+
+        if !entry.file_type().is_some_and(|kind| kind.is_dir()) {
+            continue;
+        }
+
+        add_ignore_file(&mut builder, entry.path().join(".gitignore"))?;
+        add_ignore_file(&mut builder, entry.path().join(".ignore"))?;
+    }
+
+    Ok(builder)
+}
+
+/// Fold the ignore patterns from `path` into `builder`, the same way [`GitignoreBuilder::add`]
+/// does, except that a missing file is treated as having no patterns to add instead of an error —
+/// matching how [`Gitignore::new`] treats its own root `.gitignore`/`.ignore` lookups. Most
+/// directories simply don't have one of these files (or any at all, outside a git repo), so this
+/// is the common case rather than the exceptional one.
+fn add_ignore_file(builder: &mut GitignoreBuilder, path: PathBuf) -> Result<()> {
+    let Some(error) = builder.add(&path) else {
+        return Ok(());
+    };
+
+    if error
+        .io_error()
+        .is_some_and(|e| e.kind() == io::ErrorKind::NotFound)
+    {
+        return Ok(());
+    }
+
+    Err(error.into())
+}
+
 /// Handle to the file watcher that is run in a background thread. Allows to receive change events
 /// over a channel, and can shutdown the watcher.
 pub struct Handle {
@@ -190,40 +327,68 @@ impl Handle {
 }
 
 /// Create a watcher over the given project, triggering change events for the different components
-/// of it. This takes the project's `.gitignore` file into account.
-pub fn watch(project: PathBuf) -> Result<Handle> {
+/// of it. This takes every `.gitignore`/`.ignore` file in the project, nested ones included, as well
+/// as `.git/info/exclude`, into account. See [`collect_gitignore`] for details.
+///
+/// `mode` picks between registering one non-recursive watch per directory (matching notify's
+/// default behavior, but liable to exhaust `inotify`'s `max_user_watches` limit on large trees) and
+/// registering a single recursive watch per top-level directory instead (using far fewer watch
+/// descriptors, at the cost of relying on the backend to track new sub-directories itself).
+///
+/// `backend` picks between [`notify`]'s native, kernel-notification-backed watcher and a polling
+/// one, for environments (network mounts, Docker bind mounts, WSL) where native notifications never
+/// arrive. Both produce the same stream of [`Event`]s, so the rest of the event loop doesn't need
+/// to know which one is in use.
+pub fn watch(project: PathBuf, mode: WatchMode, backend: WatchBackend) -> Result<Handle> {
     let (notify_tx, notify_rx) = flume::bounded(super::CHANNEL_SIZE);
-    let mut watcher = notify::recommended_watcher(move |ev| {
+    let event_handler = move |ev| {
         notify_tx.send(ev).ok();
-    })?;
+    };
+
+    let mut watcher: Box<dyn Watcher + Send> = match backend {
+        WatchBackend::Native => Box::new(notify::recommended_watcher(event_handler)?),
+        WatchBackend::Polling(interval) => {
+            let config = notify::Config::default().with_poll_interval(interval);
+            Box::new(notify::PollWatcher::new(event_handler, config)?)
+        }
+    };
 
     // Disable the default filters, and only really care about .gitignore patterns for
     // path exclusion.
-    let walker = WalkBuilder::new(&project)
+    let mut walker = WalkBuilder::new(&project);
+    walker
         .standard_filters(false)
         .require_git(false)
-        // .git_exclude(true) // TODO: maybe worth activating this later on
-        .git_ignore(true)
-        .build();
-
-    let gitignore = {
-        let mut builder = GitignoreBuilder::new(&project);
-        builder.add_line(None, ".git/")?;
+        .git_exclude(true)
+        .git_ignore(true);
+
+    // Only the project root's direct children are registered up front in recursive mode (the root
+    // itself is skipped below), so that ignored top-level directories (like `target/`) never get a
+    // recursive watch registered over their, potentially huge, subtree in the first place. Anything
+    // below a registered top-level directory is then discovered by the backend itself.
+    if mode == WatchMode::Recursive {
+        walker.max_depth(Some(1));
+    }
 
-        if let Some(error) = builder.add(project.join(".gitignore")) {
-            return Err(error.into());
-        }
+    let gitignore_builder = collect_gitignore(&project)?;
+    let gitignore = gitignore_builder.build()?;
 
-        builder.build()?
+    let recursive_mode = match mode {
+        WatchMode::Recursive => RecursiveMode::Recursive,
+        WatchMode::NonRecursive => RecursiveMode::NonRecursive,
     };
 
-    for entry in walker {
+    // In recursive mode, the root entry itself is skipped, as it is covered by watching its
+    // (non-ignored) top-level children recursively instead.
+    let skip_root = usize::from(mode == WatchMode::Recursive);
+
+    for entry in walker.build().skip(skip_root) {
         let entry = entry?;
         let path = entry.path().strip_prefix(&project).unwrap_or(entry.path());
 
-        trace!(path = %path.display(), "added watch path");
+        trace!(path = %path.display(), mode = ?mode, "added watch path");
 
-        watcher.watch(entry.path(), RecursiveMode::NonRecursive)?;
+        watcher.watch(entry.path(), recursive_mode)?;
     }
 
     let (change_tx, change_rx) = flume::bounded(super::CHANNEL_SIZE);
@@ -232,10 +397,12 @@ pub fn watch(project: PathBuf) -> Result<Handle> {
     let mut watcher = ProjectWatcher {
         project,
         watcher,
+        gitignore_builder,
         gitignore,
         notify_rx,
         change_tx,
         shutdown: shutdown_rx,
+        mode,
     };
 
     let task = thread::spawn(move || {
@@ -270,7 +437,7 @@ mod tests {
     #[test]
     fn create_watcher() -> Result<()> {
         let dir = env::current_dir()?.join("sample");
-        watch(dir)?.shutdown();
+        watch(dir, WatchMode::NonRecursive, WatchBackend::Native)?.shutdown();
         Ok(())
     }
 
@@ -281,7 +448,7 @@ mod tests {
         let test_txt = temp.child("test.txt");
         test_txt.touch()?;
 
-        let watcher = watch(temp.path().to_owned())?;
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
 
         test_txt.write_str("hello")?;
 
@@ -299,7 +466,7 @@ mod tests {
         temp.child(".gitignore").touch()?;
         temp.child("a").create_dir_all()?;
 
-        let watcher = watch(temp.path().to_owned())?;
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
 
         fs::rename(temp.join("a"), temp.join("b"))?;
 
@@ -318,7 +485,7 @@ mod tests {
         temp.child(".gitignore").touch()?;
         temp.child("a").create_dir_all()?;
 
-        let watcher = watch(temp.path().to_owned())?;
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
 
         fs::remove_dir_all(temp.join("a"))?;
 
@@ -328,4 +495,126 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn watch_once_recursive() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child(".gitignore").touch()?;
+        let test_txt = temp.child("nested/test.txt");
+        test_txt.touch()?;
+
+        let watcher = watch(temp.path().to_owned(), WatchMode::Recursive, WatchBackend::Native)?;
+
+        test_txt.write_str("hello")?;
+
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+        assert_eq!(None, watcher.try_recv());
+
+        watcher.shutdown();
+
+        Ok(())
+    }
+
+    #[test]
+    fn new_nested_dir_recursive() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child(".gitignore").touch()?;
+        temp.child("a").create_dir_all()?;
+
+        let watcher = watch(temp.path().to_owned(), WatchMode::Recursive, WatchBackend::Native)?;
+
+        // Creating a new directory, and a file inside it, nested below an already-watched
+        // top-level directory must be picked up without any extra bookkeeping on our side.
+        temp.child("a/b").create_dir_all()?;
+        temp.child("a/b/test.txt").write_str("hello")?;
+
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+
+        watcher.shutdown();
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_gitignore_is_honored() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child(".gitignore").touch()?;
+        temp.child("a/.gitignore").write_str("ignored.txt\n")?;
+        temp.child("a/kept.txt").touch()?;
+        temp.child("a/ignored.txt").touch()?;
+
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
+
+        temp.child("a/ignored.txt").write_str("hello")?;
+        temp.child("a/kept.txt").write_str("hello")?;
+
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+        assert_eq!(None, watcher.try_recv());
+
+        watcher.shutdown();
+
+        Ok(())
+    }
+
+    #[test]
+    fn git_exclude_is_honored() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child(".gitignore").touch()?;
+        temp.child(".git/info/exclude").write_str("ignored.txt\n")?;
+        temp.child("kept.txt").touch()?;
+        temp.child("ignored.txt").touch()?;
+
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
+
+        temp.child("ignored.txt").write_str("hello")?;
+        temp.child("kept.txt").write_str("hello")?;
+
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+        assert_eq!(None, watcher.try_recv());
+
+        watcher.shutdown();
+
+        Ok(())
+    }
+
+    #[test]
+    fn wazzup_cache_is_ignored() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child(".gitignore").touch()?;
+        temp.child(".wazzup-cache/freshness.json").touch()?;
+        let test_txt = temp.child("test.txt");
+        test_txt.touch()?;
+
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
+
+        temp.child(".wazzup-cache/freshness.json").write_str("{}")?;
+        test_txt.write_str("hello")?;
+
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+        assert_eq!(None, watcher.try_recv());
+
+        watcher.shutdown();
+
+        Ok(())
+    }
+
+    #[test]
+    fn gitignore_added_after_start_is_picked_up() -> Result<()> {
+        let temp = assert_fs::TempDir::new()?;
+        temp.child(".gitignore").touch()?;
+        temp.child("ignored.txt").touch()?;
+
+        let watcher = watch(temp.path().to_owned(), WatchMode::NonRecursive, WatchBackend::Native)?;
+
+        temp.child(".gitignore").write_str("ignored.txt\n")?;
+        assert_eq!(Some(ChangeType::Rust), watcher.recv());
+
+        temp.child("ignored.txt").write_str("hello")?;
+        assert_eq!(None, watcher.try_recv());
+
+        watcher.shutdown();
+
+        Ok(())
+    }
 }